@@ -1,24 +1,29 @@
 use std::{
     cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
+    collections::{HashMap, HashSet},
     hash::Hash,
 };
 
-use num::Zero;
+use num::{One, Zero};
+use num_bigint::BigUint;
 
 use crate::{Grid, Point2};
 
-use super::{node::Node, path::Path};
+use super::{
+    observer::SearchObserver,
+    path::Path,
+    search::{Search, SearchStats},
+};
 
-pub struct AStarMulti<C, Cost, EndCond, Neighbors, H> {
+pub struct AStarMulti<C, Cost, EndCond, Neighbors, H, O = ()> {
     // constants
     end_cond: EndCond,
     neighbors: Neighbors,
     h: H,
 
-    open_set: BinaryHeap<Node<C, Cost>>, // the set of cells we need to look at, ordered by node.cost
-    g_score: HashMap<C, Cost>,           // score for traveling to a specific node
+    search: Search<C, Cost>,
     came_from: HashMap<C, Vec<C>>,
+    observer: O,
 }
 
 impl<
@@ -30,35 +35,116 @@ impl<
     H: FnMut(&C) -> Cost,
 > AStarMulti<C, Cost, EndCond, Neighbors, H>
 {
-    pub fn new(starts: Vec<C>, end_cond: EndCond, neighbors: Neighbors, mut h: H) -> Self {
-        let mut open_set = BinaryHeap::with_capacity(starts.len());
-        let mut g_score = HashMap::with_capacity(starts.len());
-
-        for start in starts {
-            open_set.push(Node {
-                data: start.clone(),
-                cost: h(&start),
-            });
-            g_score.insert(start, Cost::zero());
-        }
+    pub fn new(starts: Vec<C>, end_cond: EndCond, neighbors: Neighbors, h: H) -> Self {
+        Self::new_observed(starts, end_cond, neighbors, h, ())
+    }
+}
 
+impl<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
+    O: SearchObserver<C, Cost>,
+> AStarMulti<C, Cost, EndCond, Neighbors, H, O>
+{
+    /// Same as [`AStarMulti::new`], but reports every push, expansion and goal
+    /// to an observer such as [`crate::SearchTracer`].
+    pub fn new_observed(
+        starts: Vec<C>,
+        end_cond: EndCond,
+        neighbors: Neighbors,
+        mut h: H,
+        mut observer: O,
+    ) -> Self {
         Self {
             end_cond,
             neighbors,
+            search: Search::new(starts, &mut h, &mut observer),
             h,
-            open_set,
-            g_score,
             came_from: HashMap::new(),
+            observer,
         }
     }
 
-    pub const fn reconstruct(&self, end: C) -> MultiPathResult<'_, C, Cost> {
+    /// Expands every node at most once. This saves work when the heuristic is
+    /// consistent, but can miss the cheapest paths when it isn't.
+    #[must_use]
+    pub fn with_closed_set(mut self) -> Self {
+        self.search.use_closed_set();
+        self
+    }
+
+    pub fn reconstruct(&self, end: C) -> MultiPathResult<'_, C, Cost> {
+        self.reconstruct_all(vec![end])
+    }
+
+    /// Combines the paths to several ends that share the same optimal score,
+    /// such as an end reached facing different directions.
+    pub const fn reconstruct_all(&self, ends: Vec<C>) -> MultiPathResult<'_, C, Cost> {
         MultiPathResult {
-            ends: end,
-            scores: &self.g_score,
+            ends,
+            scores: &self.search.g_score,
             came_from: &self.came_from,
         }
     }
+
+    /// Finds the next end and every other end reachable with the same score.
+    /// Assumes the heuristic is 0 at every end.
+    pub fn optimal_ends(&mut self) -> Option<Vec<C>> {
+        let first = self.next()?;
+        let best = self.search.g_score[&first].clone();
+        let mut ends = vec![first];
+        // stale entries are skipped before the cost is checked, so a worse
+        // end behind them stays on the frontier for the next call to next
+        while let Some((node, g)) = self.search.pop_if(&best, &mut self.observer) {
+            if let Some(end) = self.expand(node, &g)
+                && g == best
+            {
+                ends.push(end);
+            }
+        }
+        Some(ends)
+    }
+
+    pub const fn stats(&self) -> SearchStats {
+        self.search.stats
+    }
+
+    pub const fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Expands a node popped off the frontier, returning it without expanding
+    /// it if it is an end.
+    fn expand(&mut self, node: C, g: &Cost) -> Option<C> {
+        if (self.end_cond)(&node) {
+            self.observer.on_goal(&node, g);
+            return Some(node);
+        }
+
+        for (neighbor, move_cost) in (self.neighbors)(&node) {
+            let tent_g_score = g.clone() + move_cost;
+            match self
+                .search
+                .relax(&neighbor, tent_g_score, &mut self.h, &mut self.observer)
+            {
+                Ordering::Less => {
+                    self.came_from.insert(neighbor, vec![node.clone()]);
+                }
+                Ordering::Equal => {
+                    self.came_from
+                        .get_mut(&neighbor)
+                        .unwrap()
+                        .push(node.clone());
+                }
+                Ordering::Greater => {}
+            }
+        }
+        None
+    }
 }
 
 impl<
@@ -68,40 +154,15 @@ impl<
     I: IntoIterator<Item = (C, Cost)>,
     Neighbors: FnMut(&C) -> I,
     H: FnMut(&C) -> Cost,
-> Iterator for AStarMulti<C, Cost, EndCond, Neighbors, H>
+    O: SearchObserver<C, Cost>,
+> Iterator for AStarMulti<C, Cost, EndCond, Neighbors, H, O>
 {
     type Item = C;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(Node { data: node, .. }) = self.open_set.pop() {
-            if (self.end_cond)(&node) {
-                return Some(node);
-            }
-
-            for (neighbor, move_cost) in (self.neighbors)(&node) {
-                // g_score[node] will never be none because everything in open_set will be in in g_score
-                let tent_g_score = self.g_score.get(&node).unwrap().clone() + move_cost;
-                let actual_g_score = self.g_score.get(&neighbor);
-                let cmp = actual_g_score.map(|actual| tent_g_score.cmp(actual));
-
-                match cmp {
-                    None | Some(Ordering::Less) => {
-                        self.g_score.insert(neighbor.clone(), tent_g_score.clone());
-                        self.came_from.insert(neighbor.clone(), vec![node.clone()]);
-                        let new_cost = tent_g_score + (self.h)(&neighbor);
-                        self.open_set.push(Node {
-                            data: neighbor,
-                            cost: new_cost,
-                        });
-                    }
-                    Some(Ordering::Equal) => {
-                        self.came_from
-                            .get_mut(&neighbor)
-                            .unwrap()
-                            .push(node.clone());
-                    }
-                    _ => {}
-                }
+        while let Some((node, g)) = self.search.pop(&mut self.observer) {
+            if let Some(end) = self.expand(node, &g) {
+                return Some(end);
             }
         }
         None
@@ -110,7 +171,7 @@ impl<
 
 #[derive(Debug)]
 pub struct MultiPathResult<'a, C, Cost> {
-    ends: C,
+    ends: Vec<C>,
     scores: &'a HashMap<C, Cost>,
     came_from: &'a HashMap<C, Vec<C>>,
 }
@@ -120,44 +181,133 @@ impl<C: Clone + PartialEq + Eq + Hash, Cost: Clone + Ord + Zero> MultiPathResult
     where
         C: Into<Point2<usize>>,
     {
-        fn a<C: Clone + PartialEq + Eq + Hash + Into<Point2<usize>>, T: Clone>(
-            c: &C,
-            came_from: &HashMap<C, Vec<C>>,
-            grid: &mut Grid<T>,
-            path: &T,
-        ) -> Option<()> {
-            grid.set(c.clone(), path.clone())?;
-            for next in came_from.get(c).into_iter().flatten() {
-                a(next, came_from, grid, path);
-            }
-            Some(())
+        for c in self.cells_on_any_optimal_path() {
+            grid.set(c, path.clone())?;
         }
-
-        a(&self.ends, self.came_from, grid, path)
+        Some(())
     }
 
+    /// Returns every path from a start to one of the ends. The number of
+    /// paths can grow exponentially, so prefer [`Self::paths`],
+    /// [`Self::cells_on_any_optimal_path`] or [`Self::count_optimal_paths`].
+    #[must_use]
     pub fn reconstruct_paths(&self) -> Vec<Path<C>> {
-        fn a<C: Eq + Hash + Clone>(c: &C, came_from: &HashMap<C, Vec<C>>) -> Vec<Path<C>> {
-            let mut paths = vec![];
-            match came_from.get(c) {
-                None => paths.push(Path(vec![c.clone()])),
-                Some(next_list) => {
-                    for child in next_list {
-                        let ans = a(child, came_from);
-                        for mut recieved in ans {
-                            recieved.0.push(c.clone());
-                            paths.push(recieved);
-                        }
-                    }
+        self.paths().collect()
+    }
+
+    /// Lazily iterates over every path from a start to one of the ends.
+    #[must_use]
+    pub fn paths(&self) -> OptimalPaths<'_, C> {
+        OptimalPaths {
+            came_from: self.came_from,
+            ends: self.ends.iter(),
+            stack: vec![],
+        }
+    }
+
+    /// Returns every node that is on at least one optimal path.
+    #[must_use]
+    pub fn cells_on_any_optimal_path(&self) -> HashSet<C> {
+        let mut seen: HashSet<C> = self.ends.iter().cloned().collect();
+        let mut stack = self.ends.iter().collect::<Vec<_>>();
+        while let Some(c) = stack.pop() {
+            for prev in self.came_from.get(c).into_iter().flatten() {
+                if seen.insert(prev.clone()) {
+                    stack.push(prev);
                 }
             }
-            paths
         }
-        a(&self.ends, self.came_from)
+        seen
+    }
+
+    /// Counts the optimal paths without building them.
+    #[must_use]
+    pub fn count_optimal_paths(&self) -> BigUint {
+        self.count_with(|a, b| a + b)
+    }
+
+    /// Counts the optimal paths without building them, stopping at
+    /// [`u64::MAX`] instead of overflowing.
+    #[must_use]
+    pub fn count_optimal_paths_saturating(&self) -> u64 {
+        self.count_with(|a: &u64, b: &u64| a.saturating_add(*b))
+    }
+
+    fn count_with<N: Clone + Zero + One>(&self, add: impl Fn(&N, &N) -> N) -> N {
+        // the number of paths to a node is the sum over the nodes it came from,
+        // computed children first with an explicit stack to avoid recursion
+        let mut counts: HashMap<&C, N> = HashMap::new();
+        let mut stack: Vec<(&C, bool)> = self.ends.iter().map(|c| (c, false)).collect();
+        while let Some((c, ready)) = stack.pop() {
+            if counts.contains_key(c) {
+                continue;
+            }
+            let prevs = self.came_from.get(c).map_or(&[][..], Vec::as_slice);
+            if prevs.is_empty() {
+                counts.insert(c, N::one());
+            } else if ready {
+                let total = prevs
+                    .iter()
+                    .fold(N::zero(), |acc, prev| add(&acc, &counts[prev]));
+                counts.insert(c, total);
+            } else {
+                stack.push((c, true));
+                stack.extend(prevs.iter().map(|prev| (prev, false)));
+            }
+        }
+        self.ends
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .fold(N::zero(), |acc, end| add(&acc, &counts[end]))
     }
 
+    #[must_use]
+    pub fn ends(&self) -> &[C] {
+        &self.ends
+    }
+
+    #[must_use]
     pub fn end_score(&self) -> &Cost {
-        self.scores.get(&self.ends).unwrap()
+        self.scores.get(&self.ends[0]).unwrap()
+    }
+}
+
+/// Iterates over the optimal paths of a [`MultiPathResult`] one at a time,
+/// walking back from each end with a depth first search.
+#[derive(Debug, Clone)]
+pub struct OptimalPaths<'a, C> {
+    came_from: &'a HashMap<C, Vec<C>>,
+    ends: std::slice::Iter<'a, C>,
+    stack: Vec<(&'a C, usize)>,
+}
+
+impl<C: Clone + Eq + Hash> Iterator for OptimalPaths<'_, C> {
+    type Item = Path<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.stack.is_empty() {
+                self.stack.push((self.ends.next()?, 0));
+            }
+            let (c, i) = self.stack.last_mut().unwrap();
+            match self.came_from.get(*c) {
+                None => {
+                    let path = self.stack.iter().rev().map(|(c, _)| (*c).clone());
+                    let path = Path(path.collect());
+                    self.stack.pop();
+                    return Some(path);
+                }
+                Some(prevs) if *i < prevs.len() => {
+                    *i += 1;
+                    let prev = &prevs[*i - 1];
+                    self.stack.push((prev, 0));
+                }
+                Some(_) => {
+                    self.stack.pop();
+                }
+            }
+        }
     }
 }
 
@@ -170,6 +320,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(unused_variables)]
     fn test() {
         let grid = Grid::from_chars_transpose(
             "
@@ -224,4 +375,144 @@ mod tests {
 
         // println!("{}", res1.end_score());
     }
+
+    #[test]
+    fn path_lengths() {
+        // a pillar in the middle leaves one route along each side
+        let grid = Grid::from_chars("S..\n.#.\n..E").unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        let mut finder = AStarMulti::new(
+            vec![Entity::new_on_grid(start, Dir::East, &grid).unwrap()],
+            |en| en.pos() == end,
+            |en| {
+                Dir::ORTHO
+                    .into_iter()
+                    .filter_map(|dir| en.set_dir(dir).step_bounded())
+                    .filter(|en| *grid.get(en.pos()).unwrap() != '#')
+                    .map(|en| (en, 1))
+                    .collect_vec()
+            },
+            |_| 0,
+        );
+        let ends = finder.optimal_ends().unwrap();
+        let result = finder.reconstruct_all(ends);
+        let paths = result.reconstruct_paths();
+        assert_eq!(paths.len(), 2);
+        for path in paths {
+            assert_eq!(path.0.len(), result.end_score() + 1);
+        }
+    }
+
+    // the first example maze from 2024 day 16, where turning costs 1000
+    const REINDEER: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[test]
+    fn reindeer() {
+        let grid = Grid::from_chars(REINDEER).unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        let mut finder = AStarMulti::new(
+            vec![Entity::new_on_grid(start, Dir::East, &grid).unwrap()],
+            |en| en.pos() == end,
+            |en| {
+                let forward = en
+                    .step_bounded()
+                    .filter(|en| *grid.get(en.pos()).unwrap() != '#')
+                    .map(|en| (en, 1));
+                [(en.turn_left(), 1000), (en.turn_right(), 1000)]
+                    .into_iter()
+                    .chain(forward)
+            },
+            |_| 0,
+        );
+        let ends = finder.optimal_ends().unwrap();
+        let result = finder.reconstruct_all(ends);
+        assert_eq!(*result.end_score(), 7036);
+        let tiles = result
+            .cells_on_any_optimal_path()
+            .into_iter()
+            .map(Entity::pos)
+            .collect::<HashSet<_>>();
+        assert_eq!(tiles.len(), 45);
+
+        let count = result.count_optimal_paths_saturating();
+        assert_eq!(count, result.paths().count() as u64);
+        assert_eq!(result.count_optimal_paths(), BigUint::from(count));
+        for path in result.paths() {
+            assert_eq!(path.0[0].pos(), start);
+            assert_eq!(path.0.last().unwrap().pos(), end);
+        }
+    }
+
+    #[test]
+    fn multiple_ends() {
+        // an open 3x3 room entered at the top left, where the bottom right can
+        // be reached facing either south or east
+        let grid = Grid::new(vec![vec!['.'; 3]; 3]).unwrap();
+        let end = Point2::new(2, 2);
+        let mut finder = AStarMulti::new(
+            vec![Entity::new_on_grid((0, 0), Dir::East, &grid).unwrap()],
+            |en| en.pos() == end,
+            |en| {
+                Dir::ORTHO
+                    .into_iter()
+                    .filter_map(|dir| en.set_dir(dir).step_bounded())
+                    .map(|en| (en, 1))
+                    .collect_vec()
+            },
+            |_| 0,
+        );
+        let ends = finder.optimal_ends().unwrap();
+        assert_eq!(
+            ends.iter().map(|en| en.dir()).collect::<HashSet<_>>(),
+            HashSet::from([Dir::East, Dir::South])
+        );
+        let result = finder.reconstruct_all(ends);
+        assert_eq!(*result.end_score(), 4);
+        assert_eq!(result.count_optimal_paths(), BigUint::from(6u32));
+        assert_eq!(result.reconstruct_paths().len(), 6);
+        assert!(result.paths().all(|p| p.0.len() == 5));
+
+        let single = finder.reconstruct(result.ends()[0]);
+        assert_eq!(single.count_optimal_paths_saturating(), 3);
+        let mut out = grid.clone();
+        result.apply(&mut out, &'O').unwrap();
+        assert!(out.find(&'.').is_none());
+    }
+
+    #[test]
+    fn stale_entry_before_worse_end() {
+        // 2 is first pushed costing 5, then reached for 2 through 1, which
+        // leaves a stale entry tied with the best end 3. The worse end 4 must
+        // still be found after it is skipped. Both orders of the first
+        // neighbors are tried since ties can come off the heap either way.
+        for first in [vec![(3, 5), (2, 5), (1, 1)], vec![(2, 5), (3, 5), (1, 1)]] {
+            let edges = |n: &u8| match n {
+                0 => first.clone(),
+                1 => vec![(2, 1)],
+                2 => vec![(4, 7)],
+                _ => vec![],
+            };
+            let mut finder = AStarMulti::new(vec![0], |n| *n >= 3, edges, |_| 0);
+            assert_eq!(finder.optimal_ends(), Some(vec![3]));
+            assert_eq!(finder.next(), Some(4));
+            assert_eq!(*finder.reconstruct(4).end_score(), 9);
+            assert_eq!(finder.next(), None);
+        }
+    }
 }
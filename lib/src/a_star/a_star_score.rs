@@ -1,12 +1,11 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    hash::Hash,
-};
+use std::hash::Hash;
 
 use num::Zero;
 
-use super::node::Node;
+use super::{
+    observer::SearchObserver,
+    search::{Search, SearchStats},
+};
 
 pub fn a_star_score<
     C: Clone + PartialEq + Eq + Hash,
@@ -15,44 +14,180 @@ pub fn a_star_score<
     I: IntoIterator<Item = (C, Cost)>,
     Neighbors: FnMut(&C) -> I,
     H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+) -> Option<Cost> {
+    score_core(starts, end_cond, neighbors, h, false, &mut ()).0
+}
+
+/// Same as [`a_star_score`], but also returns how much work the search did.
+pub fn a_star_score_stats<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+) -> (Option<Cost>, SearchStats) {
+    score_core(starts, end_cond, neighbors, h, false, &mut ())
+}
+
+/// Same as [`a_star_score`], but expands every node at most once. This saves
+/// work when the heuristic is consistent, but can miss the cheapest path when
+/// it isn't.
+pub fn a_star_score_closed<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+) -> Option<Cost> {
+    score_core(starts, end_cond, neighbors, h, true, &mut ()).0
+}
+
+/// Same as [`a_star_score`], but reports every push, expansion and goal to an
+/// observer such as [`crate::SearchTracer`].
+pub fn a_star_score_observed<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+    observer: &mut impl SearchObserver<C, Cost>,
+) -> Option<Cost> {
+    score_core(starts, end_cond, neighbors, h, false, observer).0
+}
+
+fn score_core<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
 >(
     starts: Vec<C>,
     mut end_cond: EndCond,
     mut neighbors: Neighbors,
     mut h: H,
-) -> Option<Cost> {
-    let mut open_set = BinaryHeap::with_capacity(starts.len());
-    let mut g_score = HashMap::with_capacity(starts.len());
-
-    for start in starts {
-        open_set.push(Node {
-            data: start.clone(),
-            cost: h(&start),
-        });
-        g_score.insert(start, Cost::zero());
+    closed: bool,
+    observer: &mut impl SearchObserver<C, Cost>,
+) -> (Option<Cost>, SearchStats) {
+    let mut search = Search::new(starts, &mut h, observer);
+    if closed {
+        search.use_closed_set();
     }
 
-    while let Some(Node { data: node, .. }) = open_set.pop() {
+    while let Some((node, g)) = search.pop(observer) {
         if end_cond(&node) {
-            return Some(g_score.get(&node).unwrap().clone());
+            observer.on_goal(&node, &g);
+            return (Some(g), search.stats);
         }
 
         for (neighbor, move_cost) in (neighbors)(&node) {
-            // g_score[node] will never be none because everything in open_set will be in in g_score
-            let tent_g_score = g_score.get(&node).unwrap().clone() + move_cost;
-            let actual_g_score = g_score.get(&neighbor);
-            let is_better = actual_g_score
-                .map(|actual| tent_g_score.cmp(actual))
-                .is_none_or(Ordering::is_lt);
-
-            if is_better {
-                g_score.insert(neighbor.clone(), tent_g_score.clone());
-                open_set.push(Node {
-                    data: neighbor.clone(),
-                    cost: tent_g_score + (h)(&neighbor),
-                });
-            }
+            search.relax(&neighbor, g.clone() + move_cost, &mut h, observer);
         }
     }
-    None
+    (None, search.stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AStarMulti, a_star_single, a_star_single_closed};
+
+    use super::*;
+
+    #[test]
+    fn skips_stale_entries() {
+        // 0 -> 1 directly costs 5, but going through 2 costs 2
+        let edges = |n: &u32| match n {
+            0 => vec![(1, 5), (2, 1)],
+            2 => vec![(1, 1)],
+            1 => vec![(3, 1)],
+            _ => vec![],
+        };
+        assert_eq!(a_star_score(vec![0], |n| *n == 3, edges, |_| 0), Some(3));
+        let (score, stats) = a_star_score_stats(vec![0], |_| false, edges, |_| 0);
+        assert_eq!(score, None);
+        assert_eq!(
+            stats,
+            SearchStats {
+                expanded: 4,
+                skipped: 1,
+                max_frontier: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn closed_set() {
+        // the heuristic at 2 overestimates the step to 3, so 3 is first
+        // reached the long way through 1 and has to be expanded again
+        let edges = |n: &u32| match n {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(3, 3)],
+            2 => vec![(3, 1)],
+            3 => vec![(4, 3)],
+            _ => vec![],
+        };
+        let h = |n: &u32| if *n == 2 { 4 } else { 0 };
+        let end = |n: &u32| *n == 4;
+
+        let open = a_star_single(vec![0], end, edges, h).unwrap();
+        assert_eq!(*open.end_score(), 5);
+        assert_eq!((open.stats().expanded, open.stats().skipped), (6, 0));
+
+        // closing 3 after its first expansion skips the cheaper route to it
+        let closed = a_star_single_closed(vec![0], end, edges, h).unwrap();
+        assert_eq!(*closed.end_score(), 7);
+        assert_eq!((closed.stats().expanded, closed.stats().skipped), (5, 0));
+        // the path matches the score instead of the cheaper route found later
+        let walked: u32 = closed
+            .path()
+            .0
+            .windows(2)
+            .map(|w| {
+                edges(&w[0])
+                    .into_iter()
+                    .find(|(n, _)| *n == w[1])
+                    .unwrap()
+                    .1
+            })
+            .sum();
+        assert_eq!(walked, *closed.end_score());
+        assert_eq!(closed.path().0, vec![0, 1, 3, 4]);
+        assert_eq!(a_star_score_closed(vec![0], end, edges, h), Some(7));
+        assert_eq!(a_star_score(vec![0], end, edges, h), Some(5));
+
+        let mut multi = AStarMulti::new(vec![0], end, edges, h).with_closed_set();
+        assert_eq!(multi.next(), Some(4));
+        assert_eq!(multi.stats().expanded, 5);
+        let result = multi.reconstruct(4);
+        assert_eq!(*result.end_score(), 7);
+        let paths: Vec<_> = result.paths().map(|p| p.0).collect();
+        assert_eq!(paths, vec![vec![0, 1, 3, 4]]);
+        let mut multi = AStarMulti::new(vec![0], end, edges, h);
+        assert_eq!(multi.next(), Some(4));
+        assert_eq!(multi.stats().expanded, 6);
+    }
 }
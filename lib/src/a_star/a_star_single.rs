@@ -1,14 +1,14 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    hash::Hash,
-};
+use std::{collections::HashMap, hash::Hash};
 
 use num::Zero;
 
 use crate::{Grid, Point2};
 
-use super::{node::Node, path::Path};
+use super::{
+    observer::SearchObserver,
+    path::Path,
+    search::{Search, SearchStats},
+};
 
 pub fn a_star_single<
     C: Clone + PartialEq + Eq + Hash,
@@ -17,50 +17,91 @@ pub fn a_star_single<
     I: IntoIterator<Item = (C, Cost)>,
     Neighbors: FnMut(&C) -> I,
     H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+) -> Option<SinglePathResult<C, Cost>> {
+    single_core(starts, end_cond, neighbors, h, false, &mut ())
+}
+
+/// Same as [`a_star_single`], but expands every node at most once. This saves
+/// work when the heuristic is consistent, but can miss the cheapest path when
+/// it isn't.
+pub fn a_star_single_closed<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+) -> Option<SinglePathResult<C, Cost>> {
+    single_core(starts, end_cond, neighbors, h, true, &mut ())
+}
+
+/// Same as [`a_star_single`], but reports every push, expansion and goal to
+/// an observer such as [`crate::SearchTracer`].
+pub fn a_star_single_observed<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
+>(
+    starts: Vec<C>,
+    end_cond: EndCond,
+    neighbors: Neighbors,
+    h: H,
+    observer: &mut impl SearchObserver<C, Cost>,
+) -> Option<SinglePathResult<C, Cost>> {
+    single_core(starts, end_cond, neighbors, h, false, observer)
+}
+
+fn single_core<
+    C: Clone + PartialEq + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    EndCond: FnMut(&C) -> bool,
+    I: IntoIterator<Item = (C, Cost)>,
+    Neighbors: FnMut(&C) -> I,
+    H: FnMut(&C) -> Cost,
 >(
     starts: Vec<C>,
     mut end_cond: EndCond,
     mut neighbors: Neighbors,
     mut h: H,
+    closed: bool,
+    observer: &mut impl SearchObserver<C, Cost>,
 ) -> Option<SinglePathResult<C, Cost>> {
-    let mut open_set = BinaryHeap::with_capacity(starts.len());
-    let mut g_score = HashMap::with_capacity(starts.len());
-
-    for start in starts {
-        open_set.push(Node {
-            data: start.clone(),
-            cost: h(&start),
-        });
-        g_score.insert(start, Cost::zero());
+    let mut search = Search::new(starts, &mut h, observer);
+    if closed {
+        search.use_closed_set();
     }
-
     let mut came_from = HashMap::new();
 
-    while let Some(Node { data: node, .. }) = open_set.pop() {
+    while let Some((node, g)) = search.pop(observer) {
         if (end_cond)(&node) {
+            observer.on_goal(&node, &g);
             return Some(SinglePathResult {
                 end: node,
-                scores: g_score,
+                scores: search.g_score,
                 came_from,
+                stats: search.stats,
             });
         }
 
         for (neighbor, move_cost) in (neighbors)(&node) {
-            // g_score[node] will never be none because everything in open_set will be in in g_score
-            let tent_g_score = g_score.get(&node).unwrap().clone() + move_cost;
-            let actual_g_score = g_score.get(&neighbor);
-            let is_better = actual_g_score
-                .map(|actual| tent_g_score.cmp(actual))
-                .is_none_or(Ordering::is_lt);
-
-            if is_better {
-                g_score.insert(neighbor.clone(), tent_g_score.clone());
-                came_from.insert(neighbor.clone(), node.clone());
-                let new_cost = tent_g_score + (h)(&neighbor);
-                open_set.push(Node {
-                    data: neighbor,
-                    cost: new_cost,
-                });
+            if search
+                .relax(&neighbor, g.clone() + move_cost, &mut h, observer)
+                .is_lt()
+            {
+                came_from.insert(neighbor, node.clone());
             }
         }
     }
@@ -68,9 +109,10 @@ pub fn a_star_single<
 }
 
 pub struct SinglePathResult<C, Cost> {
-    end: C,
-    scores: HashMap<C, Cost>,
-    came_from: HashMap<C, C>,
+    pub(super) end: C,
+    pub(super) scores: HashMap<C, Cost>,
+    pub(super) came_from: HashMap<C, C>,
+    pub(super) stats: SearchStats,
 }
 
 impl<C: Clone + PartialEq + Eq + Hash, Cost: Clone + Ord + Zero> SinglePathResult<C, Cost> {
@@ -107,4 +149,8 @@ impl<C: Clone + PartialEq + Eq + Hash, Cost: Clone + Ord + Zero> SinglePathResul
     pub fn end_score(&self) -> &Cost {
         self.scores.get(&self.end).unwrap()
     }
+
+    pub const fn stats(&self) -> SearchStats {
+        self.stats
+    }
 }
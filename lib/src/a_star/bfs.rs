@@ -0,0 +1,141 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::{a_star_single::SinglePathResult, distances::Distances, search::SearchStats};
+
+struct BfsResult<C> {
+    end: Option<C>,
+    scores: HashMap<C, usize>,
+    came_from: HashMap<C, C>,
+    stats: SearchStats,
+}
+
+#[allow(clippy::similar_names)]
+fn bfs_core<C: Clone + Eq + Hash, I: IntoIterator<Item = C>>(
+    starts: Vec<C>,
+    mut end_cond: impl FnMut(&C) -> bool,
+    mut neighbors: impl FnMut(&C) -> I,
+) -> BfsResult<C> {
+    let mut scores = HashMap::with_capacity(starts.len());
+    let mut came_from = HashMap::new();
+    let mut stats = SearchStats::default();
+    let mut queue = VecDeque::with_capacity(starts.len());
+
+    for start in starts {
+        if scores.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    stats.max_frontier = queue.len();
+
+    while let Some(node) = queue.pop_front() {
+        stats.expanded += 1;
+        if end_cond(&node) {
+            return BfsResult {
+                end: Some(node),
+                scores,
+                came_from,
+                stats,
+            };
+        }
+
+        let dist = scores[&node] + 1;
+        for neighbor in neighbors(&node) {
+            if !scores.contains_key(&neighbor) {
+                scores.insert(neighbor.clone(), dist);
+                came_from.insert(neighbor.clone(), node.clone());
+                queue.push_back(neighbor);
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+    BfsResult {
+        end: None,
+        scores,
+        came_from,
+        stats,
+    }
+}
+
+/// Finds the fewest steps from any of the starts to a node matching
+/// `end_cond`, where every step costs 1. Cheaper than [`crate::a_star_single`]
+/// for unweighted searches since it uses a queue instead of a heap.
+pub fn bfs<C: Clone + Eq + Hash, I: IntoIterator<Item = C>>(
+    starts: Vec<C>,
+    end_cond: impl FnMut(&C) -> bool,
+    neighbors: impl FnMut(&C) -> I,
+) -> Option<SinglePathResult<C, usize>> {
+    let res = bfs_core(starts, end_cond, neighbors);
+    Some(SinglePathResult {
+        end: res.end?,
+        scores: res.scores,
+        came_from: res.came_from,
+        stats: res.stats,
+    })
+}
+
+/// Finds the fewest steps from any of the starts to every reachable node.
+pub fn bfs_all_distances<C: Clone + Eq + Hash, I: IntoIterator<Item = C>>(
+    starts: Vec<C>,
+    neighbors: impl FnMut(&C) -> I,
+) -> Distances<C, usize> {
+    let res = bfs_core(starts, |_| false, neighbors);
+    Distances {
+        scores: res.scores,
+        came_from: res.came_from,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dir, Point2};
+
+    use super::*;
+
+    // the example office from 2016 day 13
+    fn open_neighbors(p: Point2<usize>) -> impl Iterator<Item = Point2<usize>> {
+        Dir::ORTHO
+            .into_iter()
+            .filter_map(move |d| p.apply(d))
+            .filter(|p| {
+                let Point2 { x, y } = *p;
+                (x * x + 3 * x + 2 * x * y + y + y * y + 10)
+                    .count_ones()
+                    .is_multiple_of(2)
+            })
+    }
+
+    #[test]
+    fn office() {
+        let goal = Point2::new(7, 4);
+        let res = bfs(
+            vec![Point2::new(1, 1)],
+            |p| *p == goal,
+            |p| open_neighbors(*p),
+        )
+        .unwrap();
+        assert_eq!(*res.end_score(), 11);
+        assert_eq!(res.path().0.len(), 12);
+
+        let all = bfs_all_distances(vec![Point2::new(1, 1)], |p| {
+            open_neighbors(*p).filter(|p| p.x < 10 && p.y < 7)
+        });
+        assert_eq!(all.get(&goal), Some(&11));
+        let path = all.path_to(&goal).unwrap().0;
+        assert_eq!(path.first(), Some(&Point2::new(1, 1)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan_dist(w[1]) == 1));
+        assert!(all.path_to(&Point2::new(1, 0)).is_none());
+        assert_eq!(all.iter().filter(|(_, d)| **d <= 2).count(), 5);
+    }
+
+    #[test]
+    fn unreachable() {
+        assert!(bfs(vec![0u32], |n| *n == 5, |n| (*n < 3).then_some(n + 1)).is_none());
+        let all = bfs_all_distances(vec![0u32, 0], |n| (*n < 3).then_some(n + 1));
+        assert_eq!(all.len(), 4);
+        assert_eq!(all.path_to(&0).unwrap().0, vec![0]);
+    }
+}
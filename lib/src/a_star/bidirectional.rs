@@ -0,0 +1,163 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::Zero;
+
+use super::{path::Path, search::Search};
+
+/// Expands the cheapest node on one side, recording the cheapest meeting point
+/// with the other side seen so far.
+fn expand_side<
+    C: Clone + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    I: IntoIterator<Item = (C, Cost)>,
+>(
+    side: &mut Search<C, Cost>,
+    came_from: &mut HashMap<C, C>,
+    other: &Search<C, Cost>,
+    neighbors: &mut impl FnMut(&C) -> I,
+    best: &mut Option<(Cost, C)>,
+) {
+    let Some((node, g)) = side.pop(&mut ()) else {
+        return;
+    };
+    for (neighbor, move_cost) in neighbors(&node) {
+        let tent_g_score = g.clone() + move_cost;
+        if side
+            .relax(&neighbor, tent_g_score, &mut |_| Cost::zero(), &mut ())
+            .is_lt()
+        {
+            came_from.insert(neighbor.clone(), node.clone());
+        }
+        if let Some(rest) = other.g_score.get(&neighbor) {
+            let total = side.g_score[&neighbor].clone() + rest.clone();
+            if best.as_ref().is_none_or(|(cost, _)| total < *cost) {
+                *best = Some((total, neighbor));
+            }
+        }
+    }
+}
+
+/// Finds the cheapest path from start to goal by searching forwards from the
+/// start and backwards from the goal at the same time until the two searches
+/// meet. backward is called with a node and returns the nodes that lead to it
+/// along with the cost of that move. For undirected graphs it is the same as
+/// forward.
+pub fn bidirectional_search<
+    C: Clone + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    I: IntoIterator<Item = (C, Cost)>,
+    J: IntoIterator<Item = (C, Cost)>,
+>(
+    start: C,
+    goal: C,
+    mut forward: impl FnMut(&C) -> I,
+    mut backward: impl FnMut(&C) -> J,
+) -> Option<(Path<C>, Cost)> {
+    if start == goal {
+        return Some((Path(vec![start]), Cost::zero()));
+    }
+    let mut h = |_: &C| Cost::zero();
+    let mut fwd = Search::new(vec![start], &mut h, &mut ());
+    let mut bwd = Search::new(vec![goal], &mut h, &mut ());
+    let mut fwd_came_from = HashMap::new();
+    let mut bwd_came_from = HashMap::new();
+    let mut best: Option<(Cost, C)> = None;
+
+    while let (Some(f), Some(b)) = (fwd.peek_cost(), bwd.peek_cost()) {
+        // no path through the unexplored nodes can beat the best meeting point
+        if best
+            .as_ref()
+            .is_some_and(|(cost, _)| f.clone() + b.clone() >= *cost)
+        {
+            break;
+        }
+        if f <= b {
+            expand_side(&mut fwd, &mut fwd_came_from, &bwd, &mut forward, &mut best);
+        } else {
+            expand_side(&mut bwd, &mut bwd_came_from, &fwd, &mut backward, &mut best);
+        }
+    }
+
+    let (cost, meet) = best?;
+    let mut path = vec![meet.clone()];
+    let mut current = &meet;
+    while let Some(prev) = fwd_came_from.get(current) {
+        path.push(prev.clone());
+        current = prev;
+    }
+    path.reverse();
+    let mut current = &meet;
+    while let Some(next) = bwd_came_from.get(current) {
+        path.push(next.clone());
+        current = next;
+    }
+    Some((Path(path), cost))
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::{Dir, Grid, Point2, a_star_score, bfs_all_distances};
+
+    use super::*;
+
+    #[test]
+    fn maze() {
+        let grid = Grid::from_chars(
+            "#########
+#...#...#
+#.#.#.#.#
+#.#...#.#
+#.#####.#
+#.......#
+#########",
+        )
+        .unwrap();
+        let open = |p: &Point2<usize>| {
+            Dir::ORTHO
+                .into_iter()
+                .filter_map(|d| p.apply(d))
+                .filter(|p| grid.get(*p) == Some(&'.'))
+                .map(|p| (p, 1))
+                .collect_vec()
+        };
+        let cells = grid.find_all(&'.').collect_vec();
+        for &start in &cells {
+            let dist = bfs_all_distances(vec![start], |p| open(p).into_iter().map(|(p, _)| p));
+            for &goal in &cells {
+                let (path, cost) = bidirectional_search(start, goal, open, open).unwrap();
+                assert_eq!(Some(&cost), dist.get(&goal));
+                assert_eq!(path.0.len(), cost + 1);
+                assert_eq!((path.0[0], *path.0.last().unwrap()), (start, goal));
+                assert!(path.0.windows(2).all(|w| w[0].manhattan_dist(w[1]) == 1));
+            }
+        }
+        assert!(bidirectional_search(cells[0], Point2::new(0, 0), open, open).is_none());
+    }
+
+    #[test]
+    fn directed() {
+        // add one or double, with doubling costing more than adding
+        let forward = |n: &u64| vec![(n + 1, 1), (n * 2, 3)];
+        let backward = |n: &u64| {
+            let mut prev = vec![(n - 1, 1)];
+            if n.is_multiple_of(2) {
+                prev.push((n / 2, 3));
+            }
+            prev.into_iter().filter(|(p, _)| *p > 0)
+        };
+        for goal in [1, 2, 7, 100, 1234] {
+            let expected = a_star_score(vec![1], |n| *n == goal, forward, |_| 0);
+            let (path, cost) = bidirectional_search(1, goal, forward, backward).unwrap();
+            assert_eq!(Some(cost), expected);
+            let walked: u64 = path
+                .0
+                .iter()
+                .tuple_windows()
+                .map(|(a, b)| if *b == a + 1 { 1 } else { 3 })
+                .sum();
+            assert_eq!(walked, cost);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::Zero;
+
+use super::{distances::Distances, search::Search};
+
+/// Finds the cheapest route from any of the starts to every reachable node.
+pub fn dijkstra_all<
+    C: Clone + Eq + Hash,
+    Cost: Clone + Ord + Zero,
+    I: IntoIterator<Item = (C, Cost)>,
+>(
+    starts: Vec<C>,
+    mut neighbors: impl FnMut(&C) -> I,
+) -> Distances<C, Cost> {
+    let mut h = |_: &C| Cost::zero();
+    let mut search = Search::new(starts, &mut h, &mut ());
+    let mut came_from = HashMap::new();
+
+    while let Some((node, g)) = search.pop(&mut ()) {
+        for (neighbor, move_cost) in neighbors(&node) {
+            if search
+                .relax(&neighbor, g.clone() + move_cost, &mut h, &mut ())
+                .is_lt()
+            {
+                came_from.insert(neighbor, node.clone());
+            }
+        }
+    }
+    Distances {
+        scores: search.g_score,
+        came_from,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    use super::*;
+
+    #[test]
+    fn routes() {
+        let graph = Graph::<&str, u32>::parse_weighted(
+            "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141\nCork to Galway = 10",
+            false,
+        )
+        .unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let dist = dijkstra_all(vec![id("Dublin")], |n| graph.successors(*n));
+        assert_eq!(dist.len(), 3);
+        assert_eq!(dist.get(&id("Belfast")), Some(&141));
+        assert_eq!(dist.get(&id("London")), Some(&464));
+        assert_eq!(dist.get(&id("Cork")), None);
+
+        let dist = dijkstra_all(vec![id("London")], |n| graph.successors(*n));
+        assert_eq!(
+            dist.path_to(&id("Belfast")).unwrap().0,
+            vec![id("London"), id("Belfast")]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::path::Path;
+
+/// The result of searching everything reachable from the starts: the cost of
+/// the cheapest route to every reached node, and the node each route came
+/// from.
+#[derive(Debug, Clone)]
+pub struct Distances<C, Cost> {
+    pub(super) scores: HashMap<C, Cost>,
+    pub(super) came_from: HashMap<C, C>,
+}
+
+impl<C: Clone + Eq + Hash, Cost> Distances<C, Cost> {
+    /// Returns the cost of reaching a node, or None if it wasn't reached.
+    pub fn get(&self, node: &C) -> Option<&Cost> {
+        self.scores.get(node)
+    }
+
+    pub fn contains(&self, node: &C) -> bool {
+        self.scores.contains_key(node)
+    }
+
+    /// Returns the number of nodes reached, including the starts.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Iterates over every reached node and its cost.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &Cost)> {
+        self.scores.iter()
+    }
+
+    #[must_use]
+    pub const fn scores(&self) -> &HashMap<C, Cost> {
+        &self.scores
+    }
+
+    /// Returns a cheapest path from one of the starts to a node, or None if it
+    /// wasn't reached.
+    pub fn path_to(&self, node: &C) -> Option<Path<C>> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        let mut current = node;
+        while let Some(next) = self.came_from.get(current) {
+            path.push(next.clone());
+            current = next;
+        }
+        path.reverse();
+        Some(Path(path))
+    }
+}
+
+impl<C, Cost> IntoIterator for Distances<C, Cost> {
+    type Item = (C, Cost);
+    type IntoIter = std::collections::hash_map::IntoIter<C, Cost>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.scores.into_iter()
+    }
+}
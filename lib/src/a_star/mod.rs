@@ -1,9 +1,21 @@
 mod a_star_multi;
 mod a_star_score;
 mod a_star_single;
+mod bfs;
+mod bidirectional;
+mod dijkstra;
+mod distances;
 mod node;
+mod observer;
 mod path;
+mod search;
 
 pub use a_star_multi::*;
 pub use a_star_score::*;
 pub use a_star_single::*;
+pub use bfs::*;
+pub use bidirectional::*;
+pub use dijkstra::*;
+pub use distances::*;
+pub use observer::*;
+pub use search::SearchStats;
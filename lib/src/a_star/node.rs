@@ -4,6 +4,7 @@ use std::cmp::Ordering;
 pub struct Node<C, Cost> {
     pub data: C,
     pub cost: Cost,
+    pub g: Cost, // score when the node was pushed, used to spot stale entries
 }
 
 impl<C: Eq, Cost: Ord> Ord for Node<C, Cost> {
@@ -0,0 +1,202 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{Grid, Point2};
+
+/// Hooks called as a search runs, for debugging what it explored. Every
+/// method does nothing by default, and `()` is the observer used when none is
+/// given.
+pub trait SearchObserver<C, Cost> {
+    /// Called when a node is taken off the frontier with its score.
+    fn on_expand(&mut self, _node: &C, _g: &Cost) {}
+
+    /// Called when a node is added to the frontier with its score and its
+    /// score plus heuristic.
+    fn on_push(&mut self, _node: &C, _g: &Cost, _f: &Cost) {}
+
+    /// Called when a node matching the end condition is reached.
+    fn on_goal(&mut self, _node: &C, _g: &Cost) {}
+}
+
+impl<C, Cost> SearchObserver<C, Cost> for () {}
+
+impl<C, Cost, O: SearchObserver<C, Cost>> SearchObserver<C, Cost> for &mut O {
+    fn on_expand(&mut self, node: &C, g: &Cost) {
+        (**self).on_expand(node, g);
+    }
+
+    fn on_push(&mut self, node: &C, g: &Cost, f: &Cost) {
+        (**self).on_push(node, g, f);
+    }
+
+    fn on_goal(&mut self, node: &C, g: &Cost) {
+        (**self).on_goal(node, g);
+    }
+}
+
+/// An observer that records every node in the order it was expanded.
+#[derive(Debug, Clone)]
+pub struct SearchTracer<C, Cost> {
+    expanded: Vec<(C, Cost)>,
+    pushed: usize,
+    goals: Vec<C>,
+}
+
+impl<C, Cost> Default for SearchTracer<C, Cost> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, Cost> SearchTracer<C, Cost> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            expanded: vec![],
+            pushed: 0,
+            goals: vec![],
+        }
+    }
+
+    /// Returns every expanded node and its score, in the order they were
+    /// expanded.
+    #[must_use]
+    pub fn expanded(&self) -> &[(C, Cost)] {
+        &self.expanded
+    }
+
+    /// Returns the number of times a node was added to the frontier.
+    #[must_use]
+    pub const fn pushed(&self) -> usize {
+        self.pushed
+    }
+
+    /// Returns every goal reached, in order.
+    #[must_use]
+    pub fn goals(&self) -> &[C] {
+        &self.goals
+    }
+
+    /// Returns the set of expanded nodes.
+    #[must_use]
+    pub fn visited(&self) -> HashSet<&C>
+    where
+        C: Eq + Hash,
+    {
+        self.expanded.iter().map(|(c, _)| c).collect()
+    }
+
+    /// Draws every expanded node onto a grid.
+    pub fn apply<T: Clone>(&self, grid: &mut Grid<T>, visited: &T) -> Option<()>
+    where
+        C: Clone + Into<Point2<usize>>,
+    {
+        self.apply_with(grid, |_| visited.clone())
+    }
+
+    /// Draws every expanded node onto a grid, using f to pick the value from
+    /// the node's position in the expansion order.
+    pub fn apply_with<T: Clone>(
+        &self,
+        grid: &mut Grid<T>,
+        mut f: impl FnMut(usize) -> T,
+    ) -> Option<()>
+    where
+        C: Clone + Into<Point2<usize>>,
+    {
+        for (i, (c, _)) in self.expanded.iter().enumerate() {
+            grid.set(c.clone(), f(i))?;
+        }
+        Some(())
+    }
+}
+
+impl<C: Clone, Cost: Clone> SearchObserver<C, Cost> for SearchTracer<C, Cost> {
+    fn on_expand(&mut self, node: &C, g: &Cost) {
+        self.expanded.push((node.clone(), g.clone()));
+    }
+
+    fn on_push(&mut self, _node: &C, _g: &Cost, _f: &Cost) {
+        self.pushed += 1;
+    }
+
+    fn on_goal(&mut self, node: &C, _g: &Cost) {
+        self.goals.push(node.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AStarMulti, Dir, a_star_score_observed, a_star_single_observed};
+
+    use super::*;
+
+    const MAZE: &str = "S.#.
+.##.
+...E";
+
+    fn neighbors(grid: &Grid<char>, p: Point2<usize>) -> Vec<(Point2<usize>, u32)> {
+        Dir::ORTHO
+            .into_iter()
+            .filter_map(|d| p.apply(d))
+            .filter(|p| grid.get(*p).is_some_and(|c| *c != '#'))
+            .map(|p| (p, 1))
+            .collect()
+    }
+
+    #[test]
+    fn trace() {
+        let grid = Grid::from_chars(MAZE).unwrap();
+        let (start, end) = (grid.find(&'S').unwrap(), grid.find(&'E').unwrap());
+
+        let mut tracer = SearchTracer::new();
+        let res = a_star_single_observed(
+            vec![start],
+            |p| *p == end,
+            |p| neighbors(&grid, *p),
+            |p| p.manhattan_dist(end) as u32,
+            &mut tracer,
+        )
+        .unwrap();
+        assert_eq!(*res.end_score(), 5);
+        assert_eq!(tracer.expanded().first(), Some(&(start, 0)));
+        assert_eq!(tracer.expanded().last(), Some(&(end, 5)));
+        assert_eq!(tracer.goals(), &[end]);
+        assert_eq!(tracer.expanded().len(), res.stats().expanded);
+        // the dead end at the top right is never reached
+        assert!(!tracer.visited().contains(&Point2::new(3, 0)));
+
+        let mut out = grid.clone();
+        tracer.apply(&mut out, &'o').unwrap();
+        assert_eq!(out, Grid::from_chars("oo#.\no##.\noooo").unwrap());
+        tracer.apply_with(&mut out, |i| char::from_digit(i as u32, 10).unwrap());
+        assert_eq!(out.get((3, 2)), Some(&'6'));
+    }
+
+    #[test]
+    fn observers() {
+        let grid = Grid::from_chars(MAZE).unwrap();
+        let (start, end) = (grid.find(&'S').unwrap(), grid.find(&'E').unwrap());
+
+        let mut tracer = SearchTracer::new();
+        let score = a_star_score_observed(
+            vec![start],
+            |p| *p == end,
+            |p| neighbors(&grid, *p),
+            |_| 0,
+            &mut tracer,
+        );
+        assert_eq!(score, Some(5));
+        assert!(tracer.pushed() >= tracer.expanded().len());
+
+        let mut finder = AStarMulti::new_observed(
+            vec![start],
+            |p| *p == end,
+            |p| neighbors(&grid, *p),
+            |_| 0,
+            SearchTracer::new(),
+        );
+        assert_eq!(finder.next(), Some(end));
+        assert_eq!(finder.observer().goals(), &[end]);
+        assert_eq!(finder.observer().expanded(), tracer.expanded());
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use num::Zero;
+
+use super::{node::Node, observer::SearchObserver};
+
+/// Counters describing how much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Nodes taken off the frontier and looked at, including the end node.
+    pub expanded: usize,
+    /// Frontier entries thrown away because a cheaper route to the node had
+    /// already been found, or because the node was already closed.
+    pub skipped: usize,
+    /// The largest the frontier got.
+    pub max_frontier: usize,
+}
+
+/// The frontier and scores shared by every A* search. Entries are never
+/// removed from the heap when a node's score improves, so stale entries are
+/// skipped when they are popped instead.
+pub(super) struct Search<C, Cost> {
+    open_set: BinaryHeap<Node<C, Cost>>, // the set of cells we need to look at, ordered by node.cost
+    pub g_score: HashMap<C, Cost>,       // score for traveling to a specific node
+    closed: Option<HashSet<C>>,
+    pub stats: SearchStats,
+}
+
+impl<C: Clone + Eq + Hash, Cost: Clone + Ord + Zero> Search<C, Cost> {
+    pub fn new(
+        starts: Vec<C>,
+        h: &mut impl FnMut(&C) -> Cost,
+        obs: &mut impl SearchObserver<C, Cost>,
+    ) -> Self {
+        let mut search = Self {
+            open_set: BinaryHeap::with_capacity(starts.len()),
+            g_score: HashMap::with_capacity(starts.len()),
+            closed: None,
+            stats: SearchStats::default(),
+        };
+        for start in starts {
+            search.push(start, Cost::zero(), h, obs);
+        }
+        search
+    }
+
+    /// Makes every node expand at most once. This is only safe with a
+    /// consistent heuristic, otherwise a node can be closed before its
+    /// cheapest route is found.
+    pub fn use_closed_set(&mut self) {
+        self.closed.get_or_insert_with(HashSet::new);
+    }
+
+    fn push(
+        &mut self,
+        data: C,
+        g: Cost,
+        h: &mut impl FnMut(&C) -> Cost,
+        obs: &mut impl SearchObserver<C, Cost>,
+    ) {
+        let cost = g.clone() + h(&data);
+        obs.on_push(&data, &g, &cost);
+        self.g_score.insert(data.clone(), g.clone());
+        self.open_set.push(Node { data, cost, g });
+        self.stats.max_frontier = self.stats.max_frontier.max(self.open_set.len());
+    }
+
+    /// Pops the next node to expand along with its score, skipping stale and
+    /// closed entries.
+    pub fn pop(&mut self, obs: &mut impl SearchObserver<C, Cost>) -> Option<(C, Cost)> {
+        self.pop_within(None, obs)
+    }
+
+    /// Same as [`Search::pop`], but leaves the next node on the frontier and
+    /// returns None if its score plus heuristic is above `max_cost`. Stale and
+    /// closed entries are skipped first, so the check is made against the
+    /// node that would actually be expanded.
+    pub fn pop_if(
+        &mut self,
+        max_cost: &Cost,
+        obs: &mut impl SearchObserver<C, Cost>,
+    ) -> Option<(C, Cost)> {
+        self.pop_within(Some(max_cost), obs)
+    }
+
+    fn pop_within(
+        &mut self,
+        max_cost: Option<&Cost>,
+        obs: &mut impl SearchObserver<C, Cost>,
+    ) -> Option<(C, Cost)> {
+        while let Some(node) = self.open_set.peek() {
+            // a cheaper route to this node was found after this entry was pushed
+            let stale = self
+                .g_score
+                .get(&node.data)
+                .is_some_and(|best| node.g > *best);
+            let closed = self
+                .closed
+                .as_ref()
+                .is_some_and(|closed| closed.contains(&node.data));
+            if stale || closed {
+                self.open_set.pop();
+                self.stats.skipped += 1;
+                continue;
+            }
+            if max_cost.is_some_and(|max| node.cost > *max) {
+                return None;
+            }
+            let Node { data, g, .. } = self.open_set.pop().unwrap();
+            if let Some(closed) = &mut self.closed {
+                closed.insert(data.clone());
+            }
+            self.stats.expanded += 1;
+            obs.on_expand(&data, &g);
+            return Some((data, g));
+        }
+        None
+    }
+
+    /// Returns the lowest score plus heuristic left on the frontier, which
+    /// may belong to a stale entry.
+    pub fn peek_cost(&self) -> Option<&Cost> {
+        self.open_set.peek().map(|node| &node.cost)
+    }
+
+    /// Offers a route to neighbor costing g, pushing it if it beats the best
+    /// known route. Returns how g compares to that route, where a node that
+    /// hasn't been seen yet counts as Less and a closed node as Greater.
+    pub fn relax(
+        &mut self,
+        neighbor: &C,
+        g: Cost,
+        h: &mut impl FnMut(&C) -> Cost,
+        obs: &mut impl SearchObserver<C, Cost>,
+    ) -> Ordering {
+        // a closed node is never expanded again, so a cheaper route to it
+        // mustn't change its score or where it came from either
+        if self
+            .closed
+            .as_ref()
+            .is_some_and(|closed| closed.contains(neighbor))
+        {
+            return Ordering::Greater;
+        }
+        let cmp = self
+            .g_score
+            .get(neighbor)
+            .map_or(Ordering::Less, |actual| g.cmp(actual));
+        if cmp.is_lt() {
+            self.push(neighbor.clone(), g, h, obs);
+        }
+        cmp
+    }
+}
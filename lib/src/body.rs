@@ -0,0 +1,273 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::integer::Roots;
+
+use crate::{Point2, Point3};
+
+/// A point type whose axes can be handled independently, so that a [`Body`]
+/// can move through it.
+pub trait Coords: Copy + Eq + Hash {
+    /// Builds a point by calling f with the matching axis of three points.
+    fn zip3(a: Self, b: Self, c: Self, f: impl Fn(i64, i64, i64) -> i64) -> Self;
+
+    /// Iterates over each axis of the point.
+    fn axes(self) -> impl Iterator<Item = i64>;
+}
+
+impl Coords for Point2<i64> {
+    fn zip3(a: Self, b: Self, c: Self, f: impl Fn(i64, i64, i64) -> i64) -> Self {
+        Point2::new(f(a.x, b.x, c.x), f(a.y, b.y, c.y))
+    }
+
+    fn axes(self) -> impl Iterator<Item = i64> {
+        [self.x, self.y].into_iter()
+    }
+}
+
+impl Coords for Point3<i64> {
+    fn zip3(a: Self, b: Self, c: Self, f: impl Fn(i64, i64, i64) -> i64) -> Self {
+        Point3::new(f(a.x, b.x, c.x), f(a.y, b.y, c.y), f(a.z, b.z, c.z))
+    }
+
+    fn axes(self) -> impl Iterator<Item = i64> {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
+/// Returns the position along one axis after t ticks, where each tick adds the
+/// acceleration to the velocity and then the velocity to the position.
+fn axis_at(p: i64, v: i64, a: i64, t: i64) -> i128 {
+    let (p, v, a, t) = (i128::from(p), i128::from(v), i128::from(a), i128::from(t));
+    p + v * t + a * t * (t + 1) / 2
+}
+
+/// The times t >= 0 where one axis of two bodies lines up.
+enum AxisMatch {
+    Always,
+    At(Vec<i64>),
+}
+
+/// Solves a t^2 + b t + c = 0 for non negative integer t.
+fn integer_roots(a: i128, b: i128, c: i128) -> AxisMatch {
+    let valid = |num: i128, den: i128| {
+        (den != 0 && num % den == 0 && num / den >= 0).then(|| i64::try_from(num / den).ok())?
+    };
+    if a == 0 {
+        if b == 0 {
+            return if c == 0 {
+                AxisMatch::Always
+            } else {
+                AxisMatch::At(vec![])
+            };
+        }
+        return AxisMatch::At(valid(-c, b).into_iter().collect());
+    }
+    let disc = b * b - 4 * a * c;
+    if disc < 0 {
+        return AxisMatch::At(vec![]);
+    }
+    let s = disc.sqrt();
+    if s * s != disc {
+        return AxisMatch::At(vec![]);
+    }
+    let mut roots: Vec<i64> = [valid(-b - s, 2 * a), valid(-b + s, 2 * a)]
+        .into_iter()
+        .flatten()
+        .collect();
+    roots.sort_unstable();
+    roots.dedup();
+    AxisMatch::At(roots)
+}
+
+/// A body moving with a constant velocity and acceleration. Each tick adds the
+/// acceleration to the velocity and then the velocity to the position, so the
+/// state after any number of ticks can be found in constant time.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Body<P: Coords> {
+    pub pos: P,
+    pub vel: P,
+    pub acc: P,
+}
+
+impl<P: Coords> Body<P> {
+    /// Creates a body with no acceleration.
+    #[must_use]
+    pub fn new(pos: P, vel: P) -> Self {
+        Self::with_acc(pos, vel, P::zip3(pos, pos, pos, |_, _, _| 0))
+    }
+
+    /// Creates a body with an acceleration.
+    #[must_use]
+    pub const fn with_acc(pos: P, vel: P, acc: P) -> Self {
+        Self { pos, vel, acc }
+    }
+
+    /// Returns the position after t ticks.
+    ///
+    /// # Panics
+    /// Panics if the position doesn't fit in an i64.
+    #[must_use]
+    pub fn at(&self, t: i64) -> P {
+        P::zip3(self.pos, self.vel, self.acc, |p, v, a| {
+            i64::try_from(axis_at(p, v, a, t)).expect("body position overflowed")
+        })
+    }
+
+    /// Returns the velocity after t ticks.
+    #[must_use]
+    pub fn vel_at(&self, t: i64) -> P {
+        P::zip3(self.vel, self.acc, self.acc, |v, a, _| v + a * t)
+    }
+
+    /// Returns the position after t ticks on a grid that wraps around, where
+    /// size is the length of each axis. Large values of t don't overflow.
+    #[must_use]
+    pub fn at_wrapped(&self, t: i64, size: P) -> P {
+        let t = i128::from(t);
+        let wrap = |x: i128, m: i64| x.rem_euclid(i128::from(m)) as i64;
+        // v t is periodic in t with period m, while t (t + 1) / 2 needs 2 m
+        let moved = P::zip3(self.pos, self.vel, size, |p, v, m| {
+            wrap(i128::from(p) + i128::from(v) * t.rem_euclid(m.into()), m)
+        });
+        let accel = P::zip3(self.acc, size, size, |a, m, _| {
+            let t = t.rem_euclid(2 * i128::from(m));
+            wrap(i128::from(a) * (t * (t + 1) / 2), m)
+        });
+        P::zip3(moved, accel, size, |p, a, m| (p + a).rem_euclid(m))
+    }
+
+    /// Returns the body after t ticks.
+    #[must_use]
+    pub fn advance(&self, t: i64) -> Self {
+        Self::with_acc(self.at(t), self.vel_at(t), self.acc)
+    }
+
+    /// Returns the body after a single tick.
+    #[must_use]
+    pub fn step(&self) -> Self {
+        self.advance(1)
+    }
+
+    /// Checks if two bodies are at the same position after t ticks.
+    #[must_use]
+    pub fn collides_at(&self, other: &Self, t: i64) -> bool {
+        self.at(t) == other.at(t)
+    }
+
+    /// Returns the first tick t >= 0 where two bodies are at the same
+    /// position, or None if they never meet.
+    #[must_use]
+    pub fn collision_time(&self, other: &Self) -> Option<i64> {
+        let axes = self
+            .pos
+            .axes()
+            .zip(self.vel.axes())
+            .zip(self.acc.axes())
+            .zip(other.pos.axes().zip(other.vel.axes()).zip(other.acc.axes()));
+
+        // the difference on each axis is dp + dv t + da t (t + 1) / 2, so
+        // doubling it gives da t^2 + (2 dv + da) t + 2 dp
+        let mut candidates: Option<Vec<i64>> = None;
+        for (((p1, v1), a1), ((p2, v2), a2)) in axes {
+            let (dp, dv, da) = (
+                i128::from(p1 - p2),
+                i128::from(v1 - v2),
+                i128::from(a1 - a2),
+            );
+            let AxisMatch::At(times) = integer_roots(da, 2 * dv + da, 2 * dp) else {
+                continue;
+            };
+            candidates = Some(match candidates {
+                Some(prev) => prev.into_iter().filter(|t| times.contains(t)).collect(),
+                None => times,
+            });
+        }
+        match candidates {
+            Some(times) => times.into_iter().min(),
+            None => Some(0),
+        }
+    }
+
+    /// Groups the bodies that share a position after t ticks, returning the
+    /// indices of each group with more than one body.
+    #[must_use]
+    pub fn collisions_at(bodies: &[Self], t: i64) -> Vec<Vec<usize>> {
+        let mut groups: HashMap<P, Vec<usize>> = HashMap::new();
+        for (i, body) in bodies.iter().enumerate() {
+            groups.entry(body.at(t)).or_default().push(i);
+        }
+        let mut res: Vec<_> = groups.into_values().filter(|g| g.len() > 1).collect();
+        res.sort_unstable();
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear() {
+        let robot = Body::new(Point2::new(2, 4), Point2::new(2, -3));
+        assert_eq!(robot.at(5), Point2::new(12, -11));
+        assert_eq!(robot.at_wrapped(5, Point2::new(11, 7)), Point2::new(1, 3));
+        assert_eq!(
+            robot.at_wrapped(5 + 77 * 1_000_000_000_000, Point2::new(11, 7)),
+            Point2::new(1, 3)
+        );
+        assert_eq!(robot.step().step(), robot.advance(2));
+    }
+
+    #[test]
+    fn accelerating() {
+        let body = Body::with_acc(
+            Point3::new(0, 0, 0),
+            Point3::new(0, 0, 0),
+            Point3::new(1, 0, -1),
+        );
+        let mut stepped = body;
+        for t in 1..=10 {
+            stepped = stepped.step();
+            assert_eq!(stepped.pos, body.at(t));
+            assert_eq!(stepped.vel, body.vel_at(t));
+        }
+        assert_eq!(body.at(3), Point3::new(6, 0, -6));
+        let size = Point3::new(7, 5, 3);
+        for t in 0..100 {
+            let p = body.at(t);
+            let expected = Point3::new(p.x.rem_euclid(7), p.y.rem_euclid(5), p.z.rem_euclid(3));
+            assert_eq!(body.at_wrapped(t, size), expected);
+        }
+    }
+
+    #[test]
+    fn particle_collisions() {
+        let particles = [(-6, 3), (-4, 2), (-2, 1), (3, -1)]
+            .map(|(p, v)| Body::new(Point3::new(p, 0, 0), Point3::new(v, 0, 0)));
+        assert_eq!(Body::collisions_at(&particles, 2), vec![vec![0, 1, 2]]);
+        assert!(Body::collisions_at(&particles, 1).is_empty());
+        assert_eq!(particles[0].collision_time(&particles[1]), Some(2));
+        assert!(particles[0].collides_at(&particles[2], 2));
+        // the last particle meets the first between ticks
+        assert_eq!(particles[0].collision_time(&particles[3]), None);
+    }
+
+    #[test]
+    fn collision_time() {
+        let falling = Body::with_acc(Point2::new(0, 0), Point2::new(0, 0), Point2::new(2, 0));
+        let still = Body::new(Point2::new(6, 0), Point2::new(0, 0));
+        assert_eq!(falling.collision_time(&still), Some(2));
+        assert_eq!(still.collision_time(&falling), Some(2));
+        assert_eq!(still.collision_time(&still), Some(0));
+
+        // lined up on x forever, but y only matches at t = 3
+        let a = Body::new(Point2::new(1, 0), Point2::new(1, 2));
+        let b = Body::new(Point2::new(1, 6), Point2::new(1, 0));
+        assert_eq!(a.collision_time(&b), Some(3));
+        assert_eq!(a.at(3), b.at(3));
+
+        let c = Body::new(Point2::new(0, 0), Point2::new(1, 1));
+        let d = Body::new(Point2::new(4, 2), Point2::new(0, 0));
+        assert_eq!(c.collision_time(&d), None);
+    }
+}
@@ -3,9 +3,9 @@ use std::{
     hash::Hash,
 };
 
-use num::{CheckedAdd, CheckedSub, Num};
+use num::{CheckedAdd, CheckedSub, Num, NumCast, Signed};
 
-use crate::{Dir, Grid, Offset, Point2, Vec2};
+use crate::{Dir, Grid, Offset, Point2, Rect, Vec2};
 
 /// [lower, upper), None is assumed to be unbounded
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -103,6 +103,15 @@ impl<T: Num + Copy, D: Offset> Entity<T, D> {
         })
     }
 
+    /// Creates a new entity bounded by a rect. Returns None if the position is
+    /// outside of the rect.
+    pub fn new_in_rect(pos: impl Into<Point2<T>>, dir: D, rect: Rect<T>) -> Option<Self>
+    where
+        T: PartialOrd,
+    {
+        Self::new_bounded(pos, dir, rect.min, rect.max)
+    }
+
     /// Creates a new entity with bounds from a grid. Returns None if the given
     /// point is not bounded by the grid.
     pub fn new_on_grid<C: Clone>(pos: impl Into<Point2<T>>, dir: D, grid: &Grid<C>) -> Option<Self>
@@ -262,6 +271,51 @@ impl<T: Num + Copy, D: Offset> Entity<T, D> {
         self.step_n_internal(n).filter(Self::is_bounded)
     }
 
+    /// Moves to a new position reached with signed math. Signed positions
+    /// can't underflow, so this only fails when the entity has bounds and the
+    /// new position is outside them.
+    fn set_pos_signed(self, pos: Point2<T>) -> Option<Self>
+    where
+        T: PartialOrd,
+    {
+        let en = self.set_pos_internal(pos);
+        (en.bounds.is_none() || en.is_bounded()).then_some(en)
+    }
+
+    /// Moves an entity with signed coordinates by its direction. Signed
+    /// positions can't underflow, so None is only returned if the entity has
+    /// bounds and the new position would be outside them.
+    #[must_use]
+    pub fn step_signed(self) -> Option<Self>
+    where
+        T: Signed + NumCast + PartialOrd,
+    {
+        self.step_n_signed(1)
+    }
+
+    /// Moves an entity with signed coordinates by its direction n steps. None
+    /// is only returned if the entity has bounds and the new position would be
+    /// outside them.
+    #[must_use]
+    pub fn step_n_signed(self, n: isize) -> Option<Self>
+    where
+        T: Signed + NumCast + PartialOrd,
+    {
+        let a: Vec2 = self.dir.into();
+        self.set_pos_signed(self.pos.apply_signed(a * n))
+    }
+
+    /// Applies a direction to an entity with signed coordinates without
+    /// updating the entity's actual direction. None is only returned if the
+    /// entity has bounds and the new position would be outside them.
+    #[must_use]
+    pub fn slide_signed(self, dir: impl Offset) -> Option<Self>
+    where
+        T: Signed + NumCast + PartialOrd,
+    {
+        self.set_pos_signed(self.pos.apply_signed(dir))
+    }
+
     fn slide_internal(self, dir: Dir) -> Option<Self>
     where
         T: CheckedAdd + CheckedSub + TryFrom<isize>,
@@ -332,6 +386,36 @@ impl<T: Num + Copy, D: Offset> From<Entity<T, D>> for Point2<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn signed() {
+        let en = Entity::new((0i64, 0), Dir::North).step_signed().unwrap();
+        assert_eq!(en.pos(), (0, -1).into());
+        let en = en.turn_left().step_n_signed(4).unwrap();
+        assert_eq!(en.pos(), (-4, -1).into());
+        assert_eq!(
+            en.slide_signed(Dir::South).unwrap().tuple(),
+            ((-4, 0).into(), Dir::West)
+        );
+    }
+
+    #[test]
+    fn signed_bounded() {
+        let en = Entity::new_bounded((1i64, 1), Dir::North, (0, 0), (5, 5)).unwrap();
+        let en = en.step_signed().unwrap();
+        assert_eq!(en.pos(), (1, 0).into());
+        assert_eq!(en.step_signed(), None);
+        assert_eq!(en.step_n_signed(-4).map(Entity::pos), Some((1, 4).into()));
+        assert_eq!(en.step_n_signed(-5), None);
+        assert_eq!(
+            en.slide_signed(Dir::West).map(Entity::pos),
+            Some((0, 0).into())
+        );
+        assert_eq!(
+            en.slide_signed(Dir::West).unwrap().slide_signed(Dir::West),
+            None
+        );
+    }
+
     #[test]
     fn display() {
         assert_eq!(
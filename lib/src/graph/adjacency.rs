@@ -0,0 +1,313 @@
+use std::{borrow::Borrow, collections::HashMap, error::Error, hash::Hash, str::FromStr};
+
+use derive_more::derive::Display;
+
+/// A graph whose nodes are interned, so that every node name gets a dense id
+/// starting from zero. Edges are stored as adjacency lists of ids, which makes
+/// ids cheap to use as search states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<N: Eq + Hash, E = ()> {
+    directed: bool,
+    names: Vec<N>,
+    ids: HashMap<N, usize>,
+    adj: Vec<Vec<(usize, E)>>,
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    /// Creates an empty graph where edges go one way.
+    #[must_use]
+    pub fn new_directed() -> Self {
+        Self {
+            directed: true,
+            names: vec![],
+            ids: HashMap::new(),
+            adj: vec![],
+        }
+    }
+
+    /// Creates an empty graph where every edge goes both ways.
+    #[must_use]
+    pub fn new_undirected() -> Self {
+        Self::with_direction(false)
+    }
+
+    /// Creates an empty graph that is directed or undirected depending on
+    /// directed.
+    #[must_use]
+    pub fn with_direction(directed: bool) -> Self {
+        Self {
+            directed,
+            ..Self::new_directed()
+        }
+    }
+
+    #[must_use]
+    pub const fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the number of nodes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns the number of edges. Each undirected edge is counted once.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        let total = self.adj.iter().map(Vec::len).sum::<usize>();
+        if self.directed {
+            total
+        } else {
+            let loops = self
+                .adj
+                .iter()
+                .enumerate()
+                .map(|(a, edges)| edges.iter().filter(|(b, _)| a == *b).count())
+                .sum::<usize>();
+            // self loops are only stored once
+            (total - loops) / 2 + loops
+        }
+    }
+
+    /// Adds a node if it doesn't exist yet, returning its id.
+    pub fn add_node(&mut self, name: N) -> usize {
+        if let Some(&id) = self.ids.get(&name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.clone(), id);
+        self.names.push(name);
+        self.adj.push(vec![]);
+        id
+    }
+
+    /// Gets the id of a node.
+    pub fn id<Q: Hash + Eq + ?Sized>(&self, name: &Q) -> Option<usize>
+    where
+        N: Borrow<Q>,
+    {
+        self.ids.get(name).copied()
+    }
+
+    /// Gets the name of a node from its id.
+    #[must_use]
+    pub fn name(&self, id: usize) -> &N {
+        &self.names[id]
+    }
+
+    /// Iterates over every node id and name.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &N)> + Clone {
+        self.names.iter().enumerate()
+    }
+
+    /// Adds an edge between two ids. For undirected graphs the edge is added
+    /// in both directions.
+    pub fn add_edge_ids(&mut self, a: usize, b: usize, weight: E)
+    where
+        E: Clone,
+    {
+        if !self.directed && a != b {
+            self.adj[b].push((a, weight.clone()));
+        }
+        self.adj[a].push((b, weight));
+    }
+
+    /// Adds an edge between two nodes, adding the nodes if they don't exist.
+    /// Returns the ids of both nodes.
+    pub fn add_edge(&mut self, a: N, b: N, weight: E) -> (usize, usize)
+    where
+        E: Clone,
+    {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.add_edge_ids(a, b, weight);
+        (a, b)
+    }
+
+    /// Iterates over the ids a node has edges to along with their weights.
+    pub fn neighbors(&self, id: usize) -> impl Iterator<Item = (usize, &E)> + Clone {
+        self.adj[id].iter().map(|(b, e)| (*b, e))
+    }
+
+    /// Iterates over the ids a node has edges to.
+    pub fn neighbor_ids(&self, id: usize) -> impl Iterator<Item = usize> + Clone {
+        self.adj[id].iter().map(|(b, _)| *b)
+    }
+
+    /// Iterates over neighbours and owned weights, in the shape the neighbour
+    /// function of [`crate::a_star_single`] and friends expects.
+    pub fn successors(&self, id: usize) -> impl Iterator<Item = (usize, E)> + Clone
+    where
+        E: Clone,
+    {
+        self.adj[id].iter().cloned()
+    }
+
+    /// Gets the weight of the edge from a to b.
+    #[must_use]
+    pub fn edge(&self, a: usize, b: usize) -> Option<&E> {
+        self.adj[a].iter().find(|(n, _)| *n == b).map(|(_, e)| e)
+    }
+
+    /// Iterates over every edge as (from, to, weight). Undirected edges are
+    /// yielded once, with from <= to.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, &E)> + Clone {
+        let directed = self.directed;
+        self.adj.iter().enumerate().flat_map(move |(a, edges)| {
+            edges
+                .iter()
+                .filter(move |(b, _)| directed || a <= *b)
+                .map(move |(b, e)| (a, *b, e))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("Error parsing Graph: \"{}\" is not a valid line", _0)]
+pub struct GraphParseError(String);
+impl Error for GraphParseError {}
+
+impl<'a> Graph<&'a str, ()> {
+    /// Parses lines of the form "a -> b, c", where sep is the text between the
+    /// node and the list of its neighbours, such as " -> " or " <-> ".
+    pub fn parse_lists(input: &'a str, sep: &str, directed: bool) -> Result<Self, GraphParseError> {
+        let mut graph = Self::with_direction(directed);
+        for line in input.lines() {
+            let (from, to) = line
+                .split_once(sep)
+                .ok_or_else(|| GraphParseError(line.to_string()))?;
+            let from = graph.add_node(from.trim());
+            for to in to.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                let to = graph.add_node(to);
+                // undirected lists usually mention each edge from both ends
+                if directed || graph.edge(from, to).is_none() {
+                    graph.add_edge_ids(from, to, ());
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Parses lines of the form "a-b", where sep is the text between the two
+    /// nodes.
+    pub fn parse_pairs(input: &'a str, sep: &str, directed: bool) -> Result<Self, GraphParseError> {
+        let mut graph = Self::with_direction(directed);
+        for line in input.lines() {
+            let (a, b) = line
+                .split_once(sep)
+                .ok_or_else(|| GraphParseError(line.to_string()))?;
+            graph.add_edge(a.trim(), b.trim(), ());
+        }
+        Ok(graph)
+    }
+}
+
+impl<'a, E: Clone + FromStr> Graph<&'a str, E> {
+    /// Parses weighted edges of the form "a to b = 5".
+    pub fn parse_weighted(input: &'a str, directed: bool) -> Result<Self, GraphParseError> {
+        let mut graph = Self::with_direction(directed);
+        for line in input.lines() {
+            let err = || GraphParseError(line.to_string());
+            let (nodes, weight) = line.split_once(" = ").ok_or_else(err)?;
+            let (a, b) = nodes.split_once(" to ").ok_or_else(err)?;
+            let weight = weight.trim().parse().map_err(|_| err())?;
+            graph.add_edge(a.trim(), b.trim(), weight);
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::{a_star_score, a_star_single};
+
+    use super::*;
+
+    const PIPES: &str = "0 <-> 2
+1 <-> 1
+2 <-> 0, 3, 4
+3 <-> 2, 4
+4 <-> 2, 3, 6
+5 <-> 6
+6 <-> 4, 5";
+
+    const ROUTES: &str = "London to Dublin = 464
+London to Belfast = 518
+Dublin to Belfast = 141";
+
+    #[test]
+    fn interning() {
+        let mut graph: Graph<&str, u32> = Graph::new_undirected();
+        assert_eq!(graph.add_node("a"), 0);
+        assert_eq!(graph.add_edge("b", "a", 3), (1, 0));
+        assert_eq!(graph.add_node("b"), 1);
+        assert_eq!(graph.id("b"), Some(1));
+        assert_eq!(graph.id("c"), None);
+        assert_eq!(*graph.name(1), "b");
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.edge(0, 1), Some(&3));
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn lists() {
+        let graph = Graph::parse_lists(PIPES, " <-> ", false).unwrap();
+        assert_eq!(graph.len(), 7);
+        assert_eq!(graph.edge_count(), 7);
+        let zero = graph.id("0").unwrap();
+        let reachable = pathfinding::prelude::bfs_reach(zero, |n| graph.neighbor_ids(*n)).count();
+        assert_eq!(reachable, 6);
+
+        let graph =
+            Graph::parse_lists("broadcaster -> a, b\na -> b\nb -> con", " -> ", true).unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.edge_count(), 4);
+        let b = graph.id("b").unwrap();
+        assert_eq!(
+            graph.neighbor_ids(b).map(|n| *graph.name(n)).collect_vec(),
+            vec!["con"]
+        );
+        assert!(Graph::parse_lists("a, b", " -> ", true).is_err());
+    }
+
+    #[test]
+    fn pairs() {
+        let graph = Graph::parse_pairs("kh-tc\nqp-kh\nde-cg", "-", false).unwrap();
+        let kh = graph.id("kh").unwrap();
+        assert_eq!(graph.neighbor_ids(kh).count(), 2);
+        assert_eq!(graph.edges().count(), 3);
+    }
+
+    #[test]
+    fn weighted() {
+        let graph = Graph::<&str, u32>::parse_weighted(ROUTES, false).unwrap();
+        assert_eq!(graph.edge_count(), 3);
+        let (london, belfast) = (graph.id("London").unwrap(), graph.id("Belfast").unwrap());
+        assert_eq!(graph.edge(belfast, london), Some(&518));
+
+        let score = a_star_score(
+            vec![london],
+            |n| *n == belfast,
+            |n| graph.successors(*n),
+            |_| 0,
+        );
+        assert_eq!(score, Some(518));
+        let path = a_star_single(
+            vec![graph.id("Dublin").unwrap()],
+            |n| *n == belfast,
+            |n| graph.successors(*n),
+            |_| 0,
+        )
+        .unwrap()
+        .path();
+        assert_eq!(path.0.len(), 2);
+        assert!(Graph::<&str, u32>::parse_weighted("a to b = x", false).is_err());
+    }
+}
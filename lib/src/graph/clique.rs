@@ -0,0 +1,303 @@
+use std::hash::Hash;
+
+use crate::Graph;
+
+/// A fixed size set of node ids stored one bit per node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn empty(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn full(len: usize) -> Self {
+        let mut bits = Self::empty(len);
+        for n in 0..len {
+            bits.insert(n);
+        }
+        bits
+    }
+
+    fn insert(&mut self, n: usize) {
+        self.0[n / 64] |= 1 << (n % 64);
+    }
+
+    fn remove(&mut self, n: usize) {
+        self.0[n / 64] &= !(1 << (n % 64));
+    }
+
+    fn and(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+    }
+
+    fn and_not(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|w| *w == 0)
+    }
+
+    fn count(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// Bron-Kerbosch with pivoting over a bitset adjacency matrix. When prune is
+/// set only the largest cliques are kept, and branches that can't reach that
+/// size are skipped.
+struct BronKerbosch {
+    adj: Vec<Bits>,
+    prune: bool,
+    clique: Vec<usize>,
+    found: Vec<Vec<usize>>,
+}
+
+impl BronKerbosch {
+    fn visit(&mut self, candidates: Bits, excluded: Bits) {
+        let best = self.found.first().map_or(0, Vec::len);
+        if self.prune && self.clique.len() + candidates.count() < best {
+            return;
+        }
+        if candidates.is_empty() && excluded.is_empty() {
+            if self.prune && self.clique.len() > best {
+                self.found.clear();
+            }
+            self.found.push(self.clique.clone());
+            return;
+        }
+        // every maximal clique contains the pivot or one of its non neighbors
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|&u| candidates.and(&self.adj[u]).count())
+            .unwrap();
+        let (mut candidates, mut excluded) = (candidates, excluded);
+        for v in candidates
+            .and_not(&self.adj[pivot])
+            .iter()
+            .collect::<Vec<_>>()
+        {
+            self.clique.push(v);
+            self.visit(candidates.and(&self.adj[v]), excluded.and(&self.adj[v]));
+            self.clique.pop();
+            candidates.remove(v);
+            excluded.insert(v);
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash + Ord, E> Graph<N, E> {
+    /// Builds the neighbors of each node as a bitset, ignoring edge direction
+    /// and self loops.
+    fn adjacency_bits(&self) -> Vec<Bits> {
+        let mut adj = vec![Bits::empty(self.len()); self.len()];
+        for (a, b, _) in self.edges() {
+            if a != b {
+                adj[a].insert(b);
+                adj[b].insert(a);
+            }
+        }
+        adj
+    }
+
+    /// Sorts the nodes of each clique by name, then the cliques themselves.
+    fn sort_by_names(&self, mut cliques: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        for clique in &mut cliques {
+            clique.sort_by_key(|&n| self.name(n));
+        }
+        cliques.sort_by(|a, b| {
+            a.iter()
+                .map(|&n| self.name(n))
+                .cmp(b.iter().map(|&n| self.name(n)))
+        });
+        cliques
+    }
+
+    fn bron_kerbosch(&self, prune: bool) -> Vec<Vec<usize>> {
+        // the empty clique isn't worth reporting when there are no nodes
+        if self.is_empty() {
+            return vec![];
+        }
+        let mut search = BronKerbosch {
+            adj: self.adjacency_bits(),
+            prune,
+            clique: vec![],
+            found: vec![],
+        };
+        search.visit(Bits::full(self.len()), Bits::empty(self.len()));
+        self.sort_by_names(search.found)
+    }
+
+    /// Returns every clique that can't be grown by adding another node. Each
+    /// clique is sorted by node name, and the cliques are sorted too. Edge
+    /// direction is ignored.
+    #[must_use]
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        self.bron_kerbosch(false)
+    }
+
+    /// Returns the largest clique with its nodes sorted by name, so joining
+    /// the names gives the answer directly. Ties are broken by picking the
+    /// clique whose sorted names come first. Edge direction is ignored.
+    #[must_use]
+    pub fn max_clique(&self) -> Vec<usize> {
+        self.bron_kerbosch(true)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Returns every set of k nodes that are all connected to each other,
+    /// sorted the same way as [`Graph::maximal_cliques`]. Edge direction is
+    /// ignored.
+    #[must_use]
+    pub fn k_cliques(&self, k: usize) -> Vec<Vec<usize>> {
+        fn grow(
+            adj: &[Bits],
+            k: usize,
+            clique: &mut Vec<usize>,
+            mut candidates: Bits,
+            found: &mut Vec<Vec<usize>>,
+        ) {
+            if clique.len() == k {
+                found.push(clique.clone());
+                return;
+            }
+            // only add nodes after the last one so each clique is found once
+            while clique.len() + candidates.count() >= k
+                && let Some(v) = candidates.first()
+            {
+                candidates.remove(v);
+                clique.push(v);
+                grow(adj, k, clique, candidates.and(&adj[v]), found);
+                clique.pop();
+            }
+        }
+
+        let mut found = vec![];
+        grow(
+            &self.adjacency_bits(),
+            k,
+            &mut vec![],
+            Bits::full(self.len()),
+            &mut found,
+        );
+        self.sort_by_names(found)
+    }
+
+    /// Returns every triangle, sorted the same way as [`Graph::k_cliques`].
+    #[must_use]
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        self.k_cliques(3)
+            .into_iter()
+            .map(|t| [t[0], t[1], t[2]])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    // the example network from 2024 day 23
+    const NETWORK: &str = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+
+    #[test]
+    fn lan_party() {
+        let graph = Graph::parse_pairs(NETWORK, "-", false).unwrap();
+        let join = |clique: &[usize]| clique.iter().map(|&n| *graph.name(n)).join(",");
+
+        let triangles = graph.triangles();
+        assert_eq!(triangles.len(), 12);
+        assert_eq!(join(&triangles[0]), "aq,cg,yn");
+        let with_t = triangles
+            .iter()
+            .filter(|t| t.iter().any(|&n| graph.name(n).starts_with('t')))
+            .count();
+        assert_eq!(with_t, 7);
+
+        assert_eq!(join(&graph.max_clique()), "co,de,ka,ta");
+        let fours = graph.k_cliques(4);
+        assert_eq!(fours.iter().map(|c| join(c)).collect_vec(), ["co,de,ka,ta"]);
+        assert!(graph.k_cliques(5).is_empty());
+        assert_eq!(graph.k_cliques(1).len(), graph.len());
+    }
+
+    #[test]
+    fn maximal() {
+        let graph = Graph::parse_pairs(NETWORK, "-", false).unwrap();
+        let cliques = graph.maximal_cliques();
+        for clique in &cliques {
+            for (a, b) in clique.iter().tuple_combinations() {
+                assert!(graph.edge(*a, *b).is_some());
+            }
+            // nothing else is connected to the whole clique
+            assert!(
+                (0..graph.len())
+                    .filter(|n| !clique.contains(n))
+                    .all(|n| clique.iter().any(|&c| graph.edge(n, c).is_none()))
+            );
+        }
+        assert!(cliques.iter().map(Vec::len).max() == Some(4));
+        // every triangle is inside some maximal clique
+        for t in graph.triangles() {
+            assert!(cliques.iter().any(|c| t.iter().all(|n| c.contains(n))));
+        }
+
+        let empty = Graph::<&str>::new_undirected();
+        assert!(empty.max_clique().is_empty());
+        assert!(empty.maximal_cliques().is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+use std::hash::Hash;
+
+use crate::{Graph, UnionFind};
+
+/// Lowlink bookkeeping shared by the bridge and articulation point search.
+struct LowLink {
+    adj: Vec<Vec<usize>>,
+    order: Vec<Option<usize>>,
+    low: Vec<usize>,
+    counter: usize,
+    bridges: Vec<(usize, usize)>,
+    cut_nodes: Vec<usize>,
+}
+
+impl LowLink {
+    fn visit(&mut self, node: usize, parent: Option<usize>) {
+        self.order[node] = Some(self.counter);
+        self.low[node] = self.counter;
+        self.counter += 1;
+        let mut children = 0;
+        let mut is_cut = false;
+        // only skip one edge back to the parent so parallel edges still count
+        let mut skipped_parent = false;
+        for i in 0..self.adj[node].len() {
+            let next = self.adj[node][i];
+            if Some(next) == parent && !skipped_parent {
+                skipped_parent = true;
+                continue;
+            }
+            if let Some(seen) = self.order[next] {
+                self.low[node] = self.low[node].min(seen);
+                continue;
+            }
+            children += 1;
+            self.visit(next, Some(node));
+            self.low[node] = self.low[node].min(self.low[next]);
+            let order = self.order[node].unwrap();
+            if self.low[next] > order {
+                self.bridges.push((node.min(next), node.max(next)));
+            }
+            if parent.is_some() && self.low[next] >= order {
+                is_cut = true;
+            }
+        }
+        if is_cut || (parent.is_none() && children > 1) {
+            self.cut_nodes.push(node);
+        }
+    }
+}
+
+/// Tarjan's strongly connected components.
+struct Tarjan<'a, N: Eq + Hash, E> {
+    graph: &'a Graph<N, E>,
+    order: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    counter: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<N: Clone + Eq + Hash, E> Tarjan<'_, N, E> {
+    fn visit(&mut self, node: usize) {
+        self.order[node] = Some(self.counter);
+        self.low[node] = self.counter;
+        self.counter += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+        for next in self.graph.neighbor_ids(node) {
+            match self.order[next] {
+                None => {
+                    self.visit(next);
+                    self.low[node] = self.low[node].min(self.low[next]);
+                }
+                Some(seen) if self.on_stack[next] => {
+                    self.low[node] = self.low[node].min(seen);
+                }
+                Some(_) => {}
+            }
+        }
+        if Some(self.low[node]) == self.order[node] {
+            let mut component = vec![];
+            loop {
+                let n = self.stack.pop().unwrap();
+                self.on_stack[n] = false;
+                component.push(n);
+                if n == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            self.components.push(component);
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    /// Returns the neighbors of each node with edges going both ways and self
+    /// loops removed.
+    fn undirected_ids(&self) -> Vec<Vec<usize>> {
+        let mut adj = vec![vec![]; self.len()];
+        for (a, b, _) in self.edges().filter(|(a, b, _)| a != b) {
+            adj[a].push(b);
+            adj[b].push(a);
+        }
+        adj
+    }
+
+    fn low_link(&self) -> LowLink {
+        let mut search = LowLink {
+            adj: self.undirected_ids(),
+            order: vec![None; self.len()],
+            low: vec![0; self.len()],
+            counter: 0,
+            bridges: vec![],
+            cut_nodes: vec![],
+        };
+        for n in 0..self.len() {
+            if search.order[n].is_none() {
+                search.visit(n, None);
+            }
+        }
+        search
+    }
+
+    /// Returns the ids in each connected component, ignoring edge direction.
+    /// Components are ordered by their smallest id and the ids in a component
+    /// are sorted.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut sets = UnionFind::new(self.len());
+        for (a, b, _) in self.edges() {
+            sets.union(a, b);
+        }
+        sets.components()
+    }
+
+    /// Returns every edge whose removal would split its component, as
+    /// (a, b) with a < b, sorted. Edge direction is ignored.
+    #[must_use]
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        let mut bridges = self.low_link().bridges;
+        bridges.sort_unstable();
+        bridges
+    }
+
+    /// Returns every node whose removal would split its component, sorted.
+    /// Edge direction is ignored.
+    #[must_use]
+    pub fn articulation_points(&self) -> Vec<usize> {
+        let mut points = self.low_link().cut_nodes;
+        points.sort_unstable();
+        points
+    }
+
+    /// Returns the strongly connected components of a directed graph, where
+    /// every node in a component can reach every other one. The ids in a
+    /// component are sorted, and components come in reverse topological
+    /// order, so no edge leads from a component to a later one.
+    #[must_use]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut search = Tarjan {
+            graph: self,
+            order: vec![None; self.len()],
+            low: vec![0; self.len()],
+            on_stack: vec![false; self.len()],
+            stack: vec![],
+            counter: 0,
+            components: vec![],
+        };
+        for n in 0..self.len() {
+            if search.order[n].is_none() {
+                search.visit(n);
+            }
+        }
+        search.components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipes() {
+        // the example pipes from 2017 day 12
+        let graph = Graph::parse_lists(
+            "0 <-> 2\n1 <-> 1\n2 <-> 0, 3, 4\n3 <-> 2, 4\n4 <-> 2, 3, 6\n5 <-> 6\n6 <-> 4, 5",
+            " <-> ",
+            false,
+        )
+        .unwrap();
+        let components = graph.components();
+        assert_eq!(components.len(), 2);
+        let zero = graph.id("0").unwrap();
+        let group = components.iter().find(|c| c.contains(&zero)).unwrap();
+        assert_eq!(group.len(), 6);
+
+        let name = |n: usize| *graph.name(n);
+        let bridges: Vec<_> = graph
+            .bridges()
+            .into_iter()
+            .map(|(a, b)| {
+                let mut pair = [name(a), name(b)];
+                pair.sort_unstable();
+                pair
+            })
+            .collect();
+        assert_eq!(bridges.len(), 3);
+        for pair in [["0", "2"], ["4", "6"], ["5", "6"]] {
+            assert!(bridges.contains(&pair));
+        }
+        let mut points: Vec<_> = graph.articulation_points().into_iter().map(name).collect();
+        points.sort_unstable();
+        assert_eq!(points, ["2", "4", "6"]);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        // a doubled edge is never a bridge
+        let mut graph = Graph::<&str>::new_undirected();
+        graph.add_edge("a", "b", ());
+        graph.add_edge("a", "b", ());
+        graph.add_edge("b", "c", ());
+        assert_eq!(graph.bridges(), vec![(1, 2)]);
+        assert_eq!(graph.articulation_points(), vec![1]);
+    }
+
+    #[test]
+    fn strongly_connected() {
+        let graph = Graph::parse_lists(
+            "a -> b\nb -> c\nc -> a, d\nd -> e\ne -> d\nf -> a",
+            " -> ",
+            true,
+        )
+        .unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let sccs = graph.strongly_connected_components();
+        assert_eq!(
+            sccs,
+            vec![
+                vec![id("d"), id("e")],
+                vec![id("a"), id("b"), id("c")],
+                vec![id("f")],
+            ]
+        );
+        // every edge goes to the same or an earlier component
+        let index = |n| sccs.iter().position(|c| c.contains(&n)).unwrap();
+        for (a, b, ()) in graph.edges() {
+            assert!(index(b) <= index(a));
+        }
+        // undirected, these are the ordinary components
+        assert_eq!(graph.components().len(), 1);
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use num::Zero;
+
+use crate::Graph;
+
+/// A cut found by [`Graph::min_cut`]: the edges that split the graph in two,
+/// their total weight, and the node ids on each side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut<W> {
+    pub weight: W,
+    /// Every cut edge as (a, b) with a on the left side.
+    pub edges: Vec<(usize, usize)>,
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    /// Finds the fewest edges that need to be removed to split the graph in
+    /// two. Returns None if there are fewer than two nodes.
+    #[must_use]
+    pub fn min_cut(&self) -> Option<MinCut<usize>> {
+        self.min_cut_by(|_| 1)
+    }
+
+    /// Finds the cheapest set of edges to remove to split the graph in two
+    /// using Stoer-Wagner, where weight gives the cost of removing an edge.
+    /// Edge direction is ignored, self loops never count and weights must not
+    /// be negative. Returns None if there are fewer than two nodes.
+    pub fn min_cut_by<W: Copy + Ord + Zero>(
+        &self,
+        mut weight: impl FnMut(&E) -> W,
+    ) -> Option<MinCut<W>> {
+        if self.len() < 2 {
+            return None;
+        }
+        // every node starts out alone, and merged nodes sum their edges
+        let mut adj: Vec<HashMap<usize, W>> = vec![HashMap::new(); self.len()];
+        for (a, b, w) in self.edges().filter(|(a, b, _)| a != b) {
+            let w = weight(w);
+            for (from, to) in [(a, b), (b, a)] {
+                let total = adj[from].entry(to).or_insert_with(W::zero);
+                *total = *total + w;
+            }
+        }
+        let mut groups: Vec<Vec<usize>> = (0..self.len()).map(|n| vec![n]).collect();
+        let mut active: Vec<usize> = (0..self.len()).collect();
+        let mut best: Option<(W, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // add nodes most tightly connected to the ones added so far first,
+            // the last one's connection is the cheapest cut between the last two
+            let mut conn: HashMap<usize, W> = active.iter().map(|&n| (n, W::zero())).collect();
+            let mut heap: BinaryHeap<(W, usize)> = active.iter().map(|&n| (W::zero(), n)).collect();
+            let mut order = Vec::with_capacity(active.len());
+            while let Some((w, n)) = heap.pop() {
+                if conn.get(&n) != Some(&w) {
+                    continue;
+                }
+                conn.remove(&n);
+                order.push((n, w));
+                for (&next, &edge) in &adj[n] {
+                    if let Some(total) = conn.get_mut(&next) {
+                        *total = *total + edge;
+                        heap.push((*total, next));
+                    }
+                }
+            }
+
+            let (s, _) = order[order.len() - 2];
+            let (t, cut) = order[order.len() - 1];
+            if best.as_ref().is_none_or(|(w, _)| cut < *w) {
+                best = Some((cut, groups[t].clone()));
+            }
+
+            let merged = std::mem::take(&mut adj[t]);
+            for (next, w) in merged {
+                adj[next].remove(&t);
+                if next != s {
+                    for (from, to) in [(s, next), (next, s)] {
+                        let total = adj[from].entry(to).or_insert_with(W::zero);
+                        *total = *total + w;
+                    }
+                }
+            }
+            let moved = std::mem::take(&mut groups[t]);
+            groups[s].extend(moved);
+            active.retain(|&n| n != t);
+        }
+
+        let (weight, mut left) = best?;
+        left.sort_unstable();
+        let mut on_left = vec![false; self.len()];
+        for &n in &left {
+            on_left[n] = true;
+        }
+        let right = (0..self.len()).filter(|&n| !on_left[n]).collect();
+        let edges = self
+            .edges()
+            .filter(|(a, b, _)| on_left[*a] != on_left[*b])
+            .map(|(a, b, _)| if on_left[a] { (a, b) } else { (b, a) })
+            .collect();
+        Some(MinCut {
+            weight,
+            edges,
+            left,
+            right,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    // the example wiring diagram from 2023 day 25
+    const WIRES: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn snowverload() {
+        let mut graph = Graph::new_undirected();
+        for line in WIRES.lines() {
+            let (from, to) = line.split_once(": ").unwrap();
+            for to in to.split_whitespace() {
+                graph.add_edge(from, to, ());
+            }
+        }
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.left.len() * cut.right.len(), 54);
+        assert_eq!(cut.left.len() + cut.right.len(), graph.len());
+
+        let names: HashSet<_> = cut
+            .edges
+            .iter()
+            .map(|&(a, b)| {
+                let mut pair = [*graph.name(a), *graph.name(b)];
+                pair.sort_unstable();
+                pair
+            })
+            .collect();
+        let expected = HashSet::from([["hfx", "pzl"], ["bvb", "cmg"], ["jqt", "nvd"]]);
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn weighted() {
+        // two tight triangles held together by two light edges
+        let graph = Graph::<&str, u32>::parse_weighted(
+            "a to b = 5\nb to c = 5\nc to a = 5\nd to e = 5\ne to f = 5\nf to d = 5\na to d = 1\nc to f = 2",
+            false,
+        )
+        .unwrap();
+        let cut = graph.min_cut_by(|w| *w).unwrap();
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.edges.len(), 2);
+        let side = |ids: &[usize]| ids.iter().map(|&n| *graph.name(n)).collect::<HashSet<_>>();
+        let sides = [side(&cut.left), side(&cut.right)];
+        assert!(sides.contains(&HashSet::from(["a", "b", "c"])));
+        // counting edges instead, the two light edges tie with the two edges
+        // around b or e, so only the weight is certain
+        assert_eq!(graph.min_cut().unwrap().weight, 2);
+
+        // a disconnected graph splits for free
+        let graph = Graph::parse_pairs("a-b\nc-d", "-", false).unwrap();
+        let cut = graph.min_cut().unwrap();
+        assert_eq!((cut.weight, cut.edges.len()), (0, 0));
+        assert_eq!(Graph::<&str>::new_undirected().min_cut(), None);
+    }
+}
@@ -0,0 +1,300 @@
+use std::{hash::Hash, ops::Sub};
+
+use num::Zero;
+
+use crate::Graph;
+
+struct Dfs<'a, N: Eq + Hash, E, S, End, Extend, Bound> {
+    graph: &'a Graph<N, E>,
+    end_cond: End,
+    extend: Extend,
+    upper_bound: Bound,
+    path: Vec<usize>,
+    best: Option<(S, Vec<usize>)>,
+}
+
+impl<
+    N: Clone + Eq + Hash,
+    E,
+    S: Clone + Ord,
+    End: FnMut(usize) -> bool,
+    Extend: FnMut(&S, &E) -> S,
+    Bound: FnMut(&S, usize, u64) -> Option<S>,
+> Dfs<'_, N, E, S, End, Extend, Bound>
+{
+    fn visit(&mut self, node: usize, visited: u64, score: S) {
+        self.path.push(node);
+        let pruned = self.best.as_ref().is_some_and(|(best, _)| {
+            (self.upper_bound)(&score, node, visited).is_some_and(|bound| bound <= *best)
+        });
+        if !pruned {
+            for (next, weight) in self.graph.neighbors(node) {
+                if visited & (1 << next) == 0 {
+                    let next_score = (self.extend)(&score, weight);
+                    self.visit(next, visited | (1 << next), next_score);
+                }
+            }
+        }
+        if (self.end_cond)(node) && self.best.as_ref().is_none_or(|(best, _)| score > *best) {
+            self.best = Some((score, self.path.clone()));
+        }
+        self.path.pop();
+    }
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    /// Finds the path visiting each node at most once with the largest total
+    /// weight, starting at start and ending at any node matching `end_cond`.
+    /// Weights must not be negative. Branches that can't beat the best path
+    /// so far are pruned using the heaviest edge leaving every unvisited node.
+    ///
+    /// # Panics
+    /// Panics if the graph has more than 64 nodes.
+    pub fn longest_path(
+        &self,
+        start: usize,
+        end_cond: impl FnMut(usize) -> bool,
+    ) -> Option<(E, Vec<usize>)>
+    where
+        E: Copy + Ord + Zero + Sub<Output = E>,
+    {
+        let heaviest: Vec<E> = (0..self.len())
+            .map(|n| {
+                self.neighbors(n)
+                    .map(|(_, w)| *w)
+                    .max()
+                    .unwrap_or_else(E::zero)
+            })
+            .collect();
+        let total = heaviest.iter().fold(E::zero(), |acc, w| acc + *w);
+        self.search(
+            start,
+            end_cond,
+            E::zero(),
+            |score, weight| *score + *weight,
+            |score, node, visited| {
+                // every further edge leaves the current node or a node that
+                // hasn't been visited yet, and each node is left at most once
+                let used = (0..self.len())
+                    .filter(|&n| n != node && visited & (1 << n) != 0)
+                    .fold(E::zero(), |acc, n| acc + heaviest[n]);
+                Some(*score + (total - used))
+            },
+        )
+    }
+
+    /// Same as [`Graph::longest_path`], but with a custom objective. Each path
+    /// starts with a score of init, every edge taken updates it with extend,
+    /// and the path with the largest score wins. Tuples make lexicographic
+    /// objectives such as longest, then strongest. No pruning is done.
+    ///
+    /// # Panics
+    /// Panics if the graph has more than 64 nodes.
+    pub fn longest_path_by<S: Clone + Ord>(
+        &self,
+        start: usize,
+        end_cond: impl FnMut(usize) -> bool,
+        init: S,
+        extend: impl FnMut(&S, &E) -> S,
+    ) -> Option<(S, Vec<usize>)> {
+        self.search(start, end_cond, init, extend, |_, _, _| None)
+    }
+
+    fn search<S: Clone + Ord>(
+        &self,
+        start: usize,
+        end_cond: impl FnMut(usize) -> bool,
+        init: S,
+        extend: impl FnMut(&S, &E) -> S,
+        upper_bound: impl FnMut(&S, usize, u64) -> Option<S>,
+    ) -> Option<(S, Vec<usize>)> {
+        assert!(
+            self.len() <= 64,
+            "longest path search supports at most 64 nodes"
+        );
+        let mut dfs = Dfs {
+            graph: self,
+            end_cond,
+            extend,
+            upper_bound,
+            path: vec![],
+            best: None,
+        };
+        dfs.visit(start, 1 << start, init);
+        dfs.best
+    }
+
+    /// Finds the path with the largest total weight from start to any node
+    /// matching `end_cond` in a directed acyclic graph, in linear time.
+    /// Returns None if no such path exists or the graph has a cycle.
+    pub fn longest_path_dag(
+        &self,
+        start: usize,
+        mut end_cond: impl FnMut(usize) -> bool,
+    ) -> Option<(E, Vec<usize>)>
+    where
+        E: Copy + Ord + Zero,
+    {
+        if !self.is_directed() {
+            return None;
+        }
+        let order = self.topo_sort().ok()?;
+
+        let mut best: Vec<Option<(E, usize)>> = vec![None; self.len()];
+        best[start] = Some((E::zero(), start));
+        for n in order {
+            let Some((score, _)) = best[n] else {
+                continue;
+            };
+            for (next, weight) in self.neighbors(n) {
+                let score = score + *weight;
+                if best[next].is_none_or(|(prev, _)| score > prev) {
+                    best[next] = Some((score, n));
+                }
+            }
+        }
+
+        let (end, (score, _)) = (0..self.len())
+            .filter_map(|n| Some((n, best[n]?)))
+            .filter(|(n, _)| end_cond(*n))
+            .max_by_key(|(_, (score, _))| *score)?;
+        let mut path = vec![end];
+        let mut current = end;
+        while current != start {
+            current = best[current].unwrap().1;
+            path.push(current);
+        }
+        path.reverse();
+        Some((score, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dir, Grid, Offset, Point2};
+
+    use super::*;
+
+    // the example trails from 2023 day 23
+    const TRAILS: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    /// Shrinks the trails down to the junctions, with the length of each
+    /// corridor between them as the weight.
+    fn junctions(slopes: bool) -> (Graph<Point2<usize>, u32>, usize, usize) {
+        let grid = Grid::from_chars(TRAILS).unwrap();
+        let open = |p: Point2<usize>| grid.get(p).is_some_and(|c| *c != '#');
+        let exits = |p: Point2<usize>| {
+            Dir::ORTHO
+                .into_iter()
+                .filter(move |d| p.apply(*d).is_some_and(open))
+        };
+        let start = Point2::new(1, 0);
+        let end = Point2::new(grid.width() - 2, grid.height() - 1);
+        let nodes: Vec<_> = grid
+            .enumerate()
+            .map(|(p, _)| p)
+            .filter(|p| open(*p) && (*p == start || *p == end || exits(*p).count() > 2))
+            .collect();
+
+        let mut graph = Graph::new_directed();
+        for &node in &nodes {
+            graph.add_node(node);
+        }
+        for &node in &nodes {
+            'corridor: for dir in exits(node) {
+                let (mut prev, mut dir, mut len) = (node, dir, 0);
+                loop {
+                    let cur = prev.apply(dir).unwrap();
+                    len += 1;
+                    if slopes && Dir::try_from(*grid.get(cur).unwrap()).is_ok_and(|d| d != dir) {
+                        continue 'corridor;
+                    }
+                    if nodes.contains(&cur) {
+                        graph.add_edge(node, cur, len);
+                        continue 'corridor;
+                    }
+                    let Some(next) = exits(cur).find(|d| *d != dir.reverse()) else {
+                        continue 'corridor;
+                    };
+                    (prev, dir) = (cur, next);
+                }
+            }
+        }
+        let (start, end) = (graph.id(&start).unwrap(), graph.id(&end).unwrap());
+        (graph, start, end)
+    }
+
+    #[test]
+    fn hike() {
+        let (graph, start, end) = junctions(true);
+        let (len, path) = graph.longest_path(start, |n| n == end).unwrap();
+        assert_eq!(len, 94);
+        assert_eq!((path[0], *path.last().unwrap()), (start, end));
+        assert_eq!(
+            graph.longest_path_dag(start, |n| n == end),
+            Some((len, path))
+        );
+
+        let (graph, start, end) = junctions(false);
+        assert_eq!(graph.longest_path(start, |n| n == end).unwrap().0, 154);
+        assert_eq!(graph.longest_path_dag(start, |n| n == end), None);
+        assert_eq!(
+            graph
+                .longest_path_by(start, |n| n == end, 0, |s, w| s + w)
+                .unwrap()
+                .0,
+            154
+        );
+    }
+
+    #[test]
+    fn lexicographic() {
+        // a - b - c - d and a - e - f - d both have the most edges ending at
+        // d, but the second is stronger, while a - d is the heaviest edge
+        let graph = Graph::<&str, u32>::parse_weighted(
+            "a to b = 1\nb to c = 1\nc to d = 1\na to e = 5\ne to f = 5\nf to d = 5\na to d = 20",
+            false,
+        )
+        .unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let names = |path: Vec<usize>| path.into_iter().map(|n| *graph.name(n)).collect::<Vec<_>>();
+
+        let (weight, path) = graph.longest_path(id("a"), |_| true).unwrap();
+        assert_eq!(weight, 30);
+        assert_eq!(names(path), vec!["a", "d", "f", "e"]);
+
+        // longest, then strongest, ending at d
+        let (score, path) = graph
+            .longest_path_by(
+                id("a"),
+                |n| n == id("d"),
+                (0, 0),
+                |(len, strength), w| (len + 1, strength + w),
+            )
+            .unwrap();
+        assert_eq!(score, (3, 15));
+        assert_eq!(names(path), vec!["a", "e", "f", "d"]);
+    }
+}
@@ -0,0 +1,14 @@
+mod adjacency;
+mod clique;
+mod connectivity;
+mod cut;
+mod longest_path;
+mod topo;
+mod tsp;
+mod union_find;
+
+pub use adjacency::*;
+pub use cut::*;
+pub use topo::*;
+pub use tsp::*;
+pub use union_find::*;
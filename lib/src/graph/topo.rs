@@ -0,0 +1,275 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    error::Error,
+    hash::Hash,
+};
+
+use derive_more::derive::Display;
+
+use crate::Graph;
+
+/// Returned when a graph that should be acyclic has a cycle. Holds the ids of
+/// one cycle in the order its edges go, without repeating the first node.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("Graph has a cycle: {:?}", _0)]
+pub struct CycleError(Vec<usize>);
+impl Error for CycleError {}
+
+impl CycleError {
+    #[must_use]
+    pub fn cycle(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+/// A task placed by [`Graph::schedule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledTask {
+    pub node: usize,
+    pub worker: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// The result of [`Graph::schedule`]: every task in the order it was started,
+/// and the time the last one finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub tasks: Vec<ScheduledTask>,
+    pub finish: u64,
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degree = vec![0; self.len()];
+        for n in 0..self.len() {
+            for next in self.neighbor_ids(n) {
+                in_degree[next] += 1;
+            }
+        }
+        in_degree
+    }
+
+    /// Finds a cycle among nodes that a topological sort couldn't place.
+    /// Every such node has an edge coming in from another one, so following
+    /// those edges backwards must loop.
+    fn find_cycle(&self, placed: &HashSet<usize>) -> CycleError {
+        let mut pred = vec![None; self.len()];
+        for n in (0..self.len()).filter(|n| !placed.contains(n)) {
+            for next in self.neighbor_ids(n).filter(|next| !placed.contains(next)) {
+                pred[next] = Some(n);
+            }
+        }
+        let mut seen = vec![false; self.len()];
+        let mut current = (0..self.len()).find(|n| !placed.contains(n)).unwrap();
+        while !seen[current] {
+            seen[current] = true;
+            current = pred[current].unwrap();
+        }
+        let mut cycle = vec![current];
+        let mut n = pred[current].unwrap();
+        while n != current {
+            cycle.push(n);
+            n = pred[n].unwrap();
+        }
+        cycle.reverse();
+        CycleError(cycle)
+    }
+
+    /// Orders the nodes so that every edge goes from an earlier node to a
+    /// later one. Ties are broken by node id.
+    pub fn topo_sort(&self) -> Result<Vec<usize>, CycleError> {
+        self.topo_sort_by_key(|n| n)
+    }
+
+    /// Orders the nodes so that every edge goes from an earlier node to a
+    /// later one. Whenever several nodes could come next, the one with the
+    /// smallest key is picked, so `|n| graph.name(n)` gives the
+    /// lexicographically smallest order.
+    pub fn topo_sort_by_key<K: Ord>(
+        &self,
+        mut key: impl FnMut(usize) -> K,
+    ) -> Result<Vec<usize>, CycleError> {
+        let mut in_degree = self.in_degrees();
+        let mut ready: BinaryHeap<_> = (0..self.len())
+            .filter(|&n| in_degree[n] == 0)
+            .map(|n| Reverse((key(n), n)))
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse((_, n))) = ready.pop() {
+            order.push(n);
+            for next in self.neighbor_ids(n) {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push(Reverse((key(next), next)));
+                }
+            }
+        }
+        if order.len() < self.len() {
+            return Err(self.find_cycle(&order.into_iter().collect()));
+        }
+        Ok(order)
+    }
+
+    /// Runs every node as a task on a number of workers, where a task can only
+    /// start once every task with an edge into it has finished. Whenever a
+    /// worker is free and several tasks are ready, the one with the smallest
+    /// key is started, and free workers are used lowest id first.
+    pub fn schedule(
+        &self,
+        workers: usize,
+        duration: impl FnMut(usize) -> u64,
+    ) -> Result<Schedule, CycleError> {
+        self.schedule_by_key(workers, duration, |n| n)
+    }
+
+    /// Same as [`Graph::schedule`], but ties between ready tasks are broken by
+    /// key instead of node id.
+    ///
+    /// # Panics
+    /// Panics if there are no workers.
+    pub fn schedule_by_key<K: Ord>(
+        &self,
+        workers: usize,
+        mut duration: impl FnMut(usize) -> u64,
+        mut key: impl FnMut(usize) -> K,
+    ) -> Result<Schedule, CycleError> {
+        assert!(workers > 0, "scheduling needs at least one worker");
+        let mut in_degree = self.in_degrees();
+        let mut ready: BinaryHeap<_> = (0..self.len())
+            .filter(|&n| in_degree[n] == 0)
+            .map(|n| Reverse((key(n), n)))
+            .collect();
+        let mut free: BinaryHeap<_> = (0..workers).map(Reverse).collect();
+        let mut busy: BinaryHeap<Reverse<(u64, usize, usize)>> = BinaryHeap::new();
+        let mut tasks = Vec::with_capacity(self.len());
+        let mut time = 0;
+
+        loop {
+            while !free.is_empty()
+                && let Some(Reverse((_, node))) = ready.pop()
+            {
+                let Reverse(worker) = free.pop().unwrap();
+                let end = time + duration(node);
+                tasks.push(ScheduledTask {
+                    node,
+                    worker,
+                    start: time,
+                    end,
+                });
+                busy.push(Reverse((end, worker, node)));
+            }
+            let Some(&Reverse((end, _, _))) = busy.peek() else {
+                break;
+            };
+            time = end;
+            // finish every task ending now before handing out new ones
+            while let Some(&Reverse((end, worker, node))) = busy.peek()
+                && end == time
+            {
+                busy.pop();
+                free.push(Reverse(worker));
+                for next in self.neighbor_ids(node) {
+                    in_degree[next] -= 1;
+                    if in_degree[next] == 0 {
+                        ready.push(Reverse((key(next), next)));
+                    }
+                }
+            }
+        }
+
+        if tasks.len() < self.len() {
+            return Err(self.find_cycle(&tasks.iter().map(|t| t.node).collect()));
+        }
+        Ok(Schedule {
+            tasks,
+            finish: time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    // the example instructions from 2018 day 7
+    const STEPS: &str = "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    fn steps() -> Graph<char> {
+        let mut graph = Graph::new_directed();
+        for line in STEPS.lines() {
+            let words = line.split_whitespace().collect_vec();
+            let first = |i: usize| words[i].chars().next().unwrap();
+            graph.add_edge(first(1), first(7), ());
+        }
+        graph
+    }
+
+    #[test]
+    fn instructions() {
+        let graph = steps();
+        let names = |order: Vec<usize>| {
+            order
+                .into_iter()
+                .map(|n| *graph.name(n))
+                .collect::<String>()
+        };
+        assert_eq!(
+            names(graph.topo_sort_by_key(|n| *graph.name(n)).unwrap()),
+            "CABDFE"
+        );
+        // ids follow the order the steps were first mentioned
+        assert_eq!(names(graph.topo_sort().unwrap()), "CAFBDE");
+    }
+
+    #[test]
+    fn workers() {
+        let graph = steps();
+        let duration = |n| u64::from(*graph.name(n) as u8 - b'A' + 1);
+        let schedule = graph
+            .schedule_by_key(2, duration, |n| *graph.name(n))
+            .unwrap();
+        assert_eq!(schedule.finish, 15);
+        let started = schedule
+            .tasks
+            .iter()
+            .map(|t| *graph.name(t.node))
+            .collect::<String>();
+        assert_eq!(started, "CAFBDE");
+        let f = schedule
+            .tasks
+            .iter()
+            .find(|t| *graph.name(t.node) == 'F')
+            .unwrap();
+        assert_eq!((f.worker, f.start, f.end), (1, 3, 9));
+
+        // a single worker takes as long as every step added together
+        assert_eq!(graph.schedule(1, duration).unwrap().finish, 21);
+    }
+
+    #[test]
+    fn cycles() {
+        let graph =
+            Graph::parse_lists("a -> b\nb -> c\nc -> a\nd -> a\nc -> e", " -> ", true).unwrap();
+        let err = graph.topo_sort().unwrap_err();
+        let cycle = err.cycle();
+        assert_eq!(cycle.len(), 3);
+        for (a, b) in cycle.iter().circular_tuple_windows() {
+            assert!(graph.edge(*a, *b).is_some());
+        }
+        assert!(graph.schedule(3, |_| 1).is_err());
+
+        let mut graph = Graph::<usize>::new_directed();
+        graph.add_edge(0, 0, ());
+        assert_eq!(graph.topo_sort(), Err(CycleError(vec![0])));
+    }
+}
@@ -0,0 +1,262 @@
+use num::Zero;
+
+use crate::{Dir, Grid, Point2, bfs_all_distances, tern};
+
+/// Whether a [`Tsp`] looks for the cheapest or the most expensive route.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Objective {
+    #[default]
+    Min,
+    Max,
+}
+
+/// A route found by [`Tsp::solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tour<Cost> {
+    pub cost: Cost,
+    /// Every node in the order it is visited. Cycles don't repeat the first
+    /// node at the end.
+    pub order: Vec<usize>,
+}
+
+/// Finds the best order to visit every node of a distance matrix using the
+/// Held-Karp dynamic program, which takes O(2^n n^2) time instead of trying
+/// all n! orders. By default it finds the cheapest open path starting from
+/// any node.
+#[derive(Debug, Clone)]
+pub struct Tsp<'a, Cost> {
+    dist: &'a [Vec<Cost>],
+    cycle: bool,
+    start: Option<usize>,
+    objective: Objective,
+}
+
+impl<'a, Cost: Copy + Ord + Zero> Tsp<'a, Cost> {
+    /// Creates a solver where `dist[a][b]` is the cost of going from a to b.
+    #[must_use]
+    pub const fn new(dist: &'a [Vec<Cost>]) -> Self {
+        Self {
+            dist,
+            cycle: false,
+            start: None,
+            objective: Objective::Min,
+        }
+    }
+
+    /// Makes the route return to the node it started from.
+    #[must_use]
+    pub const fn cycle(mut self) -> Self {
+        self.cycle = true;
+        self
+    }
+
+    /// Makes the route start at a specific node.
+    #[must_use]
+    pub const fn start(mut self, start: usize) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Looks for the most expensive route instead of the cheapest.
+    #[must_use]
+    pub const fn maximize(mut self) -> Self {
+        self.objective = Objective::Max;
+        self
+    }
+
+    /// Sets whether to look for the cheapest or most expensive route.
+    #[must_use]
+    pub const fn objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    fn better(&self, a: Cost, b: Cost) -> bool {
+        match self.objective {
+            Objective::Min => a < b,
+            Objective::Max => a > b,
+        }
+    }
+
+    /// Finds the best route, or None if there are no nodes.
+    ///
+    /// # Panics
+    /// Panics if the matrix has more than 32 nodes, since the table would
+    /// never fit in memory anyway, or if the start node isn't in the matrix.
+    #[must_use]
+    pub fn solve(&self) -> Option<Tour<Cost>> {
+        let n = self.dist.len();
+        assert!(n <= 32, "Held-Karp supports at most 32 nodes");
+        if n == 0 {
+            return None;
+        }
+        assert!(
+            self.start.is_none_or(|s| s < n),
+            "start node is outside the {n} node matrix"
+        );
+        // a cycle passes through every node, so it can start anywhere
+        let start = self.start.or(self.cycle.then_some(0));
+        let full = (1usize << n) - 1;
+
+        // best[mask][last] is the best route visiting the nodes in mask and
+        // ending at last, along with the node before last
+        let mut best: Vec<Vec<Option<(Cost, usize)>>> = vec![vec![None; n]; 1 << n];
+        for first in 0..n {
+            if start.is_none_or(|s| s == first) {
+                best[1 << first][first] = Some((Cost::zero(), first));
+            }
+        }
+        for mask in 1..=full {
+            for last in 0..n {
+                let Some((cost, _)) = best[mask][last] else {
+                    continue;
+                };
+                for next in (0..n).filter(|next| mask & (1 << next) == 0) {
+                    let cost = cost + self.dist[last][next];
+                    let slot = &mut best[mask | (1 << next)][next];
+                    if slot.is_none_or(|(prev, _)| self.better(cost, prev)) {
+                        *slot = Some((cost, last));
+                    }
+                }
+            }
+        }
+
+        let (mut last, cost) = (0..n)
+            .filter_map(|last| {
+                let (cost, _) = best[full][last]?;
+                let back = tern!(self.cycle, self.dist[last][start.unwrap()], Cost::zero());
+                Some((last, cost + back))
+            })
+            .reduce(|a, b| if self.better(b.1, a.1) { b } else { a })?;
+
+        let mut order = vec![last];
+        let mut mask = full;
+        while mask.count_ones() > 1 {
+            let (_, prev) = best[mask][last].unwrap();
+            mask &= !(1 << last);
+            last = prev;
+            order.push(last);
+        }
+        order.reverse();
+        Some(Tour { cost, order })
+    }
+}
+
+/// Builds a distance matrix between points of interest on a grid, where a
+/// step can be taken in any of the four directions onto a cell matching
+/// passable. Returns None if some point can't reach another.
+pub fn distance_matrix<T>(
+    grid: &Grid<T>,
+    points: &[Point2<usize>],
+    passable: impl Fn(&T) -> bool,
+) -> Option<Vec<Vec<usize>>> {
+    points
+        .iter()
+        .map(|&from| {
+            let dist = bfs_all_distances(vec![from], |p: &Point2<usize>| {
+                Dir::ORTHO
+                    .into_iter()
+                    .filter_map(|d| p.apply(d))
+                    .filter(|p| grid.get(*p).is_some_and(&passable))
+                    .collect::<Vec<_>>()
+            });
+            points.iter().map(|to| dist.get(to).copied()).collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::Graph;
+
+    use super::*;
+
+    #[test]
+    fn routes() {
+        let graph = Graph::<&str, u32>::parse_weighted(
+            "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141",
+            false,
+        )
+        .unwrap();
+        let dist = (0..graph.len())
+            .map(|a| {
+                (0..graph.len())
+                    .map(|b| graph.edge(a, b).copied().unwrap_or(0))
+                    .collect()
+            })
+            .collect_vec();
+
+        let shortest = Tsp::new(&dist).solve().unwrap();
+        assert_eq!(shortest.cost, 605);
+        let names = shortest.order.iter().map(|n| *graph.name(*n)).collect_vec();
+        assert!(
+            names == ["London", "Dublin", "Belfast"] || names == ["Belfast", "Dublin", "London"]
+        );
+        assert_eq!(Tsp::new(&dist).maximize().solve().unwrap().cost, 982);
+
+        let from_dublin = Tsp::new(&dist)
+            .start(graph.id("Dublin").unwrap())
+            .solve()
+            .unwrap();
+        assert_eq!(from_dublin.cost, 659);
+        assert_eq!(from_dublin.order[0], graph.id("Dublin").unwrap());
+        assert_eq!(Tsp::<u32>::new(&[]).solve(), None);
+    }
+
+    #[test]
+    fn seating() {
+        // the happiness of each pair sitting together from 2015 day 13, for
+        // Alice, Bob, Carol and David
+        let happiness: [[i32; 4]; 4] = [
+            [0, 54, -79, -2],
+            [83, 0, -7, -63],
+            [-62, 60, 0, 55],
+            [46, -7, 41, 0],
+        ];
+        let dist = (0..4)
+            .map(|a| (0..4).map(|b| happiness[a][b] + happiness[b][a]).collect())
+            .collect_vec();
+        let table = Tsp::new(&dist).cycle().maximize().solve().unwrap();
+        assert_eq!(table.cost, 330);
+        assert_eq!(table.order.len(), 4);
+        assert_eq!(table.order[0], 0);
+
+        // matches trying every seating
+        let brute = (1..4)
+            .permutations(3)
+            .map(|p| {
+                let seats = [vec![0], p].concat();
+                (0..4)
+                    .map(|i| dist[seats[i]][seats[(i + 1) % 4]])
+                    .sum::<i32>()
+            })
+            .max();
+        assert_eq!(brute, Some(330));
+    }
+
+    #[test]
+    fn grid_points() {
+        // the example ducts from 2016 day 24
+        let grid = Grid::from_chars(
+            "###########
+#0.1.....2#
+#.#######.#
+#4.......3#
+###########",
+        )
+        .unwrap();
+        let points = ('0'..='4').map(|c| grid.find(&c).unwrap()).collect_vec();
+        let dist = distance_matrix(&grid, &points, |c| *c != '#').unwrap();
+        assert_eq!(dist[0][1], 2);
+        assert_eq!(dist[1][4], 4);
+
+        let tour = Tsp::new(&dist).start(0).solve().unwrap();
+        assert_eq!(tour.cost, 14);
+        assert_eq!(tour.order, vec![0, 4, 1, 2, 3]);
+        assert_eq!(Tsp::new(&dist).start(0).cycle().solve().unwrap().cost, 20);
+
+        assert_eq!(distance_matrix(&grid, &points, |c| *c == '.'), None);
+    }
+}
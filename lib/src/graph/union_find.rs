@@ -0,0 +1,308 @@
+use std::{collections::HashMap, hash::Hash};
+
+use indexmap::IndexSet;
+
+/// A disjoint set forest over the ids 0..len, using path compression and union
+/// by size so that every operation is close to constant time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// Creates len sets, each holding a single id.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// Returns the number of ids.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds a new id in a set of its own, returning the id.
+    pub fn add(&mut self) -> usize {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.size.push(1);
+        self.count += 1;
+        id
+    }
+
+    /// Returns the representative of the set holding x.
+    ///
+    /// # Panics
+    /// Panics if x is out of range.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut cur = x;
+        while self.parent[cur] != root {
+            cur = std::mem::replace(&mut self.parent[cur], root);
+        }
+        root
+    }
+
+    /// Merges the sets holding a and b. Returns false if they were already in
+    /// the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        self.count -= 1;
+        true
+    }
+
+    /// Checks if a and b are in the same set.
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the size of the set holding x.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Returns the number of disjoint sets.
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the size of every set, largest first.
+    #[must_use]
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// Returns the ids in each set. Sets are ordered by their smallest id and
+    /// the ids in a set are sorted.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = HashMap::new();
+        let mut res: Vec<Vec<usize>> = vec![];
+        for i in 0..self.len() {
+            let root = self.find(i);
+            let group = *index.entry(root).or_insert_with(|| {
+                res.push(vec![]);
+                res.len() - 1
+            });
+            res[group].push(i);
+        }
+        res
+    }
+}
+
+/// A [`UnionFind`] keyed by values instead of dense ids. Values are added the
+/// first time they are seen.
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<T: Hash + Eq> {
+    keys: IndexSet<T>,
+    sets: UnionFind,
+}
+
+impl<T: Hash + Eq> Default for KeyedUnionFind<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> KeyedUnionFind<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            keys: IndexSet::new(),
+            sets: UnionFind::new(0),
+        }
+    }
+
+    /// Returns the number of values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Adds a value in a set of its own if it isn't already present, returning
+    /// its id.
+    pub fn insert(&mut self, value: T) -> usize {
+        let (id, added) = self.keys.insert_full(value);
+        if added {
+            self.sets.add();
+        }
+        id
+    }
+
+    /// Merges the sets holding a and b, adding either if they are new.
+    /// Returns false if they were already in the same set.
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    /// Checks if a and b are in the same set. Values that were never added
+    /// are only connected to themselves.
+    pub fn connected(&mut self, a: &T, b: &T) -> bool {
+        match (self.keys.get_index_of(a), self.keys.get_index_of(b)) {
+            (Some(a), Some(b)) => self.sets.connected(a, b),
+            _ => a == b,
+        }
+    }
+
+    /// Returns the size of the set holding a value, or None if it was never
+    /// added.
+    pub fn size(&mut self, value: &T) -> Option<usize> {
+        let id = self.keys.get_index_of(value)?;
+        Some(self.sets.size(id))
+    }
+
+    /// Returns the number of disjoint sets.
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        self.sets.count()
+    }
+
+    /// Returns the size of every set, largest first.
+    #[must_use]
+    pub fn sizes(&self) -> Vec<usize> {
+        self.sets.sizes()
+    }
+
+    /// Returns the values in each set, in the order they were added.
+    pub fn components(&mut self) -> Vec<Vec<&T>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| &self.keys[i]).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::{Graph, Point3};
+
+    use super::*;
+
+    // the example junction boxes from 2025 day 8
+    const BOXES: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+
+    #[test]
+    fn dense() {
+        let mut uf = UnionFind::new(5);
+        assert_eq!(uf.count(), 5);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(3, 1));
+        assert!(!uf.union(0, 3));
+        assert!(uf.connected(3, 0));
+        assert!(!uf.connected(2, 0));
+        assert_eq!(uf.size(1), 3);
+        assert_eq!(uf.count(), 3);
+        assert_eq!(uf.sizes(), vec![3, 1, 1]);
+        assert_eq!(uf.add(), 5);
+        assert_eq!(
+            uf.components(),
+            vec![vec![0, 1, 3], vec![2], vec![4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn junction_boxes() {
+        let boxes: Vec<Point3<i64>> = BOXES.lines().map(|l| l.parse().unwrap()).collect();
+        let pairs = (0..boxes.len())
+            .tuple_combinations()
+            .sorted_by_key(|&(a, b)| boxes[a].dist_squared(boxes[b]));
+
+        let mut circuits = KeyedUnionFind::new();
+        let mut last = None;
+        for (i, (a, b)) in pairs.enumerate() {
+            if i == 10 {
+                assert_eq!(circuits.sizes().into_iter().take(3).product::<usize>(), 40);
+            }
+            circuits.union(boxes[a], boxes[b]);
+            if circuits.len() == boxes.len() && circuits.count() == 1 {
+                last = Some((boxes[a], boxes[b]));
+                break;
+            }
+        }
+        let (a, b) = last.unwrap();
+        assert_eq!(a.x * b.x, 25272);
+        assert_eq!(circuits.components().len(), 1);
+    }
+
+    #[test]
+    fn pipes() {
+        let graph = Graph::parse_lists(
+            "0 <-> 2\n1 <-> 1\n2 <-> 0, 3, 4\n3 <-> 2, 4\n4 <-> 2, 3, 6\n5 <-> 6\n6 <-> 4, 5",
+            " <-> ",
+            false,
+        )
+        .unwrap();
+        let mut groups = KeyedUnionFind::new();
+        for (a, b, ()) in graph.edges() {
+            groups.union(*graph.name(a), *graph.name(b));
+        }
+        assert_eq!(groups.size(&"0"), Some(6));
+        assert_eq!(groups.size(&"7"), None);
+        assert!(groups.connected(&"5", &"2"));
+        assert!(!groups.connected(&"1", &"2"));
+        assert_eq!(groups.count(), 2);
+        assert_eq!(
+            groups.components(),
+            vec![vec![&"0", &"2", &"3", &"4", &"6", &"5"], vec![&"1"]]
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+};
+
+use crate::{Grid, Offset, Point2, Vec2, tern};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A grid of booleans where each row is packed into u64 words. Bits past the
+/// width of a row are always kept at zero so that equality, hashing and
+/// counting can work on whole words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: usize, // words per row
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid of the given size with every cell set to c.
+    #[must_use]
+    pub fn new_filled(c: bool, width: usize, height: usize) -> Self {
+        let words = width.div_ceil(BITS);
+        let mut grid = Self {
+            width,
+            height,
+            words,
+            data: vec![tern!(c, u64::MAX, 0); words * height],
+        };
+        grid.mask();
+        grid
+    }
+
+    /// Creates a grid from rows of booleans. Returns None if the rows aren't
+    /// all the same length.
+    #[must_use]
+    pub fn from_double_iter(
+        it: impl IntoIterator<Item = impl IntoIterator<Item = bool>>,
+    ) -> Option<Self> {
+        let rows = it
+            .into_iter()
+            .map(|row| row.into_iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = rows.first().map_or(0, Vec::len);
+        if !rows.iter().all(|r| r.len() == width) {
+            return None;
+        }
+
+        let mut grid = Self::new_filled(false, width, rows.len());
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, c) in row.into_iter().enumerate() {
+                grid.set_unchecked(x, y, c);
+            }
+        }
+        Some(grid)
+    }
+
+    /// Creates a grid from a string, where on is the character for a set cell.
+    #[must_use]
+    pub fn from_chars(chars: &str, on: char) -> Option<Self> {
+        Self::from_double_iter(chars.lines().map(|row| row.chars().map(|c| c == on)))
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Clears the unused bits at the end of each row.
+    fn mask(&mut self) {
+        let rem = self.width % BITS;
+        if rem == 0 {
+            return;
+        }
+        let mask = (1 << rem) - 1;
+        for row in self.data.chunks_mut(self.words) {
+            *row.last_mut().unwrap() &= mask;
+        }
+    }
+
+    const fn index(&self, x: usize, y: usize) -> (usize, u64) {
+        (y * self.words + x / BITS, 1 << (x % BITS))
+    }
+
+    fn set_unchecked(&mut self, x: usize, y: usize, v: bool) {
+        let (i, bit) = self.index(x, y);
+        if v {
+            self.data[i] |= bit;
+        } else {
+            self.data[i] &= !bit;
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, p: impl Into<Point2<usize>>) -> Option<bool> {
+        let Point2 { x, y } = p.into();
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let (i, bit) = self.index(x, y);
+        Some(self.data[i] & bit != 0)
+    }
+
+    #[must_use]
+    pub fn get_offset(&self, p: impl Into<Point2<usize>>, offset: impl Offset) -> Option<bool> {
+        let p: Point2<_> = p.into();
+        p.apply(offset).and_then(|p| self.get(p))
+    }
+
+    pub fn set(&mut self, p: impl Into<Point2<usize>>, v: bool) -> Option<()> {
+        let Point2 { x, y } = p.into();
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.set_unchecked(x, y, v);
+        Some(())
+    }
+
+    /// Flips a cell, returning its new value.
+    pub fn toggle(&mut self, p: impl Into<Point2<usize>>) -> Option<bool> {
+        let Point2 { x, y } = p.into();
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let (i, bit) = self.index(x, y);
+        self.data[i] ^= bit;
+        Some(self.data[i] & bit != 0)
+    }
+
+    pub fn apply(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<Point2<usize>>>,
+        v: bool,
+    ) -> &mut Self {
+        for p in points {
+            self.set(p, v);
+        }
+        self
+    }
+
+    /// Returns the packed words of a row. Bit x % 64 of word x / 64 is the
+    /// cell at x.
+    #[must_use]
+    pub fn row(&self, row: usize) -> Option<&[u64]> {
+        (row < self.height).then(|| &self.data[row * self.words..(row + 1) * self.words])
+    }
+
+    /// Returns the number of set cells.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns the number of set cells in a row.
+    #[must_use]
+    pub fn count_row(&self, row: usize) -> Option<usize> {
+        self.row(row)
+            .map(|r| r.iter().map(|w| w.count_ones() as usize).sum())
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point2<usize>, bool)> + Clone {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| {
+                let (i, bit) = self.index(x, y);
+                (Point2::new(x, y), self.data[i] & bit != 0)
+            })
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> {
+        self.enumerate().map(|(_, c)| c)
+    }
+
+    /// Iterates over the positions of every set cell, skipping empty words.
+    pub fn find_all(&self) -> impl Iterator<Item = Point2<usize>> + Clone {
+        self.data.iter().enumerate().flat_map(move |(i, &word)| {
+            let y = i / self.words;
+            let base = (i % self.words) * BITS;
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let x = base + word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Point2::new(x, y)
+                })
+            })
+        })
+    }
+
+    /// Returns a copy of the grid with every cell moved by an offset. Cells
+    /// moved past the edge are dropped and cells moved in from outside are
+    /// false.
+    #[must_use]
+    pub fn shifted(&self, offset: impl Offset) -> Self {
+        let Vec2 { x: dx, y: dy } = offset.into();
+        let mut new = Self::new_filled(false, self.width, self.height);
+        if dx.unsigned_abs() >= self.width || dy.unsigned_abs() >= self.height {
+            return new;
+        }
+
+        let word_shift = dx.unsigned_abs() / BITS;
+        let bit_shift = dx.unsigned_abs() % BITS;
+        for y in 0..self.height {
+            let Some(src_y) = y.checked_add_signed(-dy).filter(|y| *y < self.height) else {
+                continue;
+            };
+            let src = self.row(src_y).unwrap();
+            let word = |i: Option<usize>| i.and_then(|i| src.get(i)).copied().unwrap_or(0);
+            let dst = &mut new.data[y * self.words..(y + 1) * self.words];
+            for (i, w) in dst.iter_mut().enumerate() {
+                *w = if dx >= 0 {
+                    let a = word(i.checked_sub(word_shift));
+                    let b = word(i.checked_sub(word_shift + 1));
+                    tern!(bit_shift == 0, a, a << bit_shift | b >> (BITS - bit_shift))
+                } else {
+                    let a = word(Some(i + word_shift));
+                    let b = word(Some(i + word_shift + 1));
+                    tern!(bit_shift == 0, a, a >> bit_shift | b << (BITS - bit_shift))
+                };
+            }
+        }
+        new.mask();
+        new
+    }
+
+    fn zip_words(&mut self, other: &Self, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "Can't combine bit grids of different sizes"
+        );
+        for (a, b) in self.data.iter_mut().zip(&other.data) {
+            *a = f(*a, *b);
+        }
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(value: &Grid<bool>) -> Self {
+        let mut grid = Self::new_filled(false, value.width(), value.height());
+        for (p, c) in value.enumerate() {
+            grid.set_unchecked(p.x, p.y, *c);
+        }
+        grid
+    }
+}
+
+impl From<Grid<bool>> for BitGrid {
+    fn from(value: Grid<bool>) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(value: &BitGrid) -> Self {
+        Grid::from_double_iter(
+            (0..value.height).map(|y| (0..value.width).map(move |x| value.get((x, y)).unwrap())),
+        )
+        .unwrap()
+    }
+}
+
+impl From<BitGrid> for Grid<bool> {
+    fn from(value: BitGrid) -> Self {
+        Self::from(&value)
+    }
+}
+
+macro_rules! bit_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl $assign_trait<&BitGrid> for BitGrid {
+            fn $assign_fn(&mut self, rhs: &BitGrid) {
+                self.zip_words(rhs, |a, b| a $op b);
+            }
+        }
+
+        impl $assign_trait for BitGrid {
+            fn $assign_fn(&mut self, rhs: BitGrid) {
+                self.zip_words(&rhs, |a, b| a $op b);
+            }
+        }
+
+        impl $trait<&BitGrid> for BitGrid {
+            type Output = BitGrid;
+            fn $fn(mut self, rhs: &BitGrid) -> Self::Output {
+                self.zip_words(rhs, |a, b| a $op b);
+                self
+            }
+        }
+
+        impl $trait for BitGrid {
+            type Output = BitGrid;
+            fn $fn(self, rhs: BitGrid) -> Self::Output {
+                self $op &rhs
+            }
+        }
+
+        impl $trait for &BitGrid {
+            type Output = BitGrid;
+            fn $fn(self, rhs: &BitGrid) -> Self::Output {
+                self.clone() $op rhs
+            }
+        }
+    };
+}
+
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl Not for BitGrid {
+    type Output = BitGrid;
+    fn not(mut self) -> Self::Output {
+        for w in &mut self.data {
+            *w = !*w;
+        }
+        self.mask();
+        self
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", tern!(self.get((x, y)).unwrap(), '#', '.'))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Dir;
+
+    use super::*;
+
+    fn glider() -> BitGrid {
+        BitGrid::from_chars(".#...\n..#..\n###..\n.....", '#').unwrap()
+    }
+
+    #[test]
+    fn get_set() {
+        let mut grid = BitGrid::new_filled(false, 100, 3);
+        assert_eq!(grid.get((99, 2)), Some(false));
+        assert_eq!(grid.get((100, 2)), None);
+        grid.set((70, 1), true).unwrap();
+        assert_eq!(grid.get((70, 1)), Some(true));
+        assert_eq!(grid.toggle((70, 1)), Some(false));
+        assert_eq!(grid.count(), 0);
+        assert_eq!(grid.set((0, 3), true), None);
+
+        let grid = BitGrid::new_filled(true, 100, 3);
+        assert_eq!(grid.count(), 300);
+        assert_eq!(grid.count_row(1), Some(100));
+    }
+
+    #[test]
+    fn grid_conversion() {
+        let grid = Grid::from_chars(".#.\n#.#").unwrap().map(|c| c == '#');
+        let bits = BitGrid::from(&grid);
+        assert_eq!(bits.count(), 3);
+        assert_eq!(bits.to_string(), ".#.\n#.#");
+        assert_eq!(Grid::from(bits), grid);
+    }
+
+    #[test]
+    fn find_all() {
+        let mut grid = BitGrid::new_filled(false, 130, 2);
+        grid.apply([(0, 0), (64, 0), (129, 1)], true);
+        assert_eq!(
+            grid.find_all().collect::<Vec<_>>(),
+            vec![(0, 0).into(), (64, 0).into(), (129, 1).into()]
+        );
+    }
+
+    #[test]
+    fn ops() {
+        let a = BitGrid::from_chars("##..", '#').unwrap();
+        let b = BitGrid::from_chars("#.#.", '#').unwrap();
+        assert_eq!((&a & &b).to_string(), "#...");
+        assert_eq!((&a | &b).to_string(), "###.");
+        assert_eq!((&a ^ &b).to_string(), ".##.");
+        assert_eq!((!&a).to_string(), "..##");
+        assert_eq!((!a.clone()).count(), 2);
+
+        let mut c = a.clone();
+        c |= b;
+        assert_eq!(c.to_string(), "###.");
+    }
+
+    #[test]
+    fn shifted() {
+        let grid = glider();
+        assert_eq!(
+            grid.shifted(Dir::East).to_string(),
+            "..#..\n...#.\n.###.\n....."
+        );
+        assert_eq!(
+            grid.shifted(Dir::West).to_string(),
+            "#....\n.#...\n##...\n....."
+        );
+        assert_eq!(
+            grid.shifted(Dir::South).to_string(),
+            ".....\n.#...\n..#..\n###.."
+        );
+        assert_eq!(
+            grid.shifted(Vec2::new(1, -1)).to_string(),
+            "...#.\n.###.\n.....\n....."
+        );
+        assert_eq!(grid.shifted(Vec2::new(5, 0)).count(), 0);
+    }
+
+    #[test]
+    fn shifted_across_words() {
+        let mut grid = BitGrid::new_filled(false, 200, 1);
+        grid.apply([(0, 0), (63, 0), (127, 0), (199, 0)], true);
+
+        let east = grid.shifted(Vec2::new(65, 0));
+        assert_eq!(
+            east.find_all().collect::<Vec<_>>(),
+            vec![(65, 0).into(), (128, 0).into(), (192, 0).into()]
+        );
+
+        let west = grid.shifted(Vec2::new(-64, 0));
+        assert_eq!(
+            west.find_all().collect::<Vec<_>>(),
+            vec![(63, 0).into(), (135, 0).into()]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use crate::{Point3, Vec3};
+
+/// A dense 3d grid stored in a single vec, indexed by x, then y, then z. Used
+/// for voxel puzzles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+    depth: usize,
+}
+
+impl<T> Grid3<T> {
+    #[must_use]
+    pub fn new_filled(c: T, width: usize, height: usize, depth: usize) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![c; width * height * depth],
+            width,
+            height,
+            depth,
+        }
+    }
+
+    /// Creates a grid by calling f for every point.
+    #[must_use]
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        depth: usize,
+        mut f: impl FnMut(Point3<usize>) -> T,
+    ) -> Self {
+        let data = (0..depth)
+            .flat_map(|z| (0..height).flat_map(move |y| (0..width).map(move |x| (x, y, z))))
+            .map(|p| f(p.into()))
+            .collect();
+        Self {
+            data,
+            width,
+            height,
+            depth,
+        }
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the size of the grid as a point, which is also the exclusive
+    /// upper bound of valid positions.
+    #[must_use]
+    pub const fn size(&self) -> Point3<usize> {
+        Point3::new(self.width, self.height, self.depth)
+    }
+
+    fn index(&self, p: Point3<usize>) -> Option<usize> {
+        p.within(Point3::default(), self.size())
+            .then_some(p.x + self.width * (p.y + self.height * p.z))
+    }
+
+    const fn point(&self, i: usize) -> Point3<usize> {
+        Point3::new(
+            i % self.width,
+            i / self.width % self.height,
+            i / (self.width * self.height),
+        )
+    }
+
+    #[must_use]
+    pub fn get(&self, p: impl Into<Point3<usize>>) -> Option<&T> {
+        self.index(p.into()).map(|i| &self.data[i])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, p: impl Into<Point3<usize>>) -> Option<&mut T> {
+        self.index(p.into()).map(|i| &mut self.data[i])
+    }
+
+    #[must_use]
+    pub fn get_offset(&self, p: impl Into<Point3<usize>>, offset: Vec3) -> Option<&T> {
+        p.into().apply(offset).and_then(|p| self.get(p))
+    }
+
+    pub fn set(&mut self, p: impl Into<Point3<usize>>, v: T) -> Option<()> {
+        *self.get_mut(p)? = v;
+        Some(())
+    }
+
+    pub fn apply(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<Point3<usize>>>,
+        new_cell: &T,
+    ) -> &mut Self
+    where
+        T: Clone,
+    {
+        for p in points {
+            self.set(p, new_cell.clone());
+        }
+        self
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point3<usize>, &T)> + Clone {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (self.point(i), c))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn count_where(&self, cmp: impl Fn(&T) -> bool) -> usize {
+        self.data.iter().filter(|c| cmp(c)).count()
+    }
+
+    pub fn count(&self, cmp: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.count_where(|c| c == cmp)
+    }
+
+    pub fn find_all<'a>(
+        &'a self,
+        cell: &'a T,
+    ) -> impl Iterator<Item = Point3<usize>> + use<'a, T> + Clone
+    where
+        T: PartialEq,
+    {
+        self.enumerate()
+            .filter_map(move |(p, c)| (c == cell).then_some(p))
+    }
+
+    /// Iterates over the neighbours of a point for each offset that stays
+    /// inside the grid.
+    pub fn with_offsets<const N: usize>(
+        &self,
+        p: impl Into<Point3<usize>>,
+        offsets: [Vec3; N],
+    ) -> impl Iterator<Item = (Point3<usize>, &T)> {
+        let p = p.into();
+        offsets
+            .into_iter()
+            .filter_map(move |offset| p.apply(offset).and_then(|p| self.get(p).map(|c| (p, c))))
+    }
+
+    /// Flood fills the region of cells equal to the starting cell, connected
+    /// by faces.
+    pub fn fill(&mut self, start: impl Into<Point3<usize>>, new: &T) -> &mut Self
+    where
+        T: Clone + PartialEq,
+    {
+        let start = start.into();
+        let target = self.get(start).unwrap().clone();
+        if target == *new {
+            return self;
+        }
+        let mut queue = vec![start];
+        while let Some(p) = queue.pop() {
+            let Some(c) = self.get_mut(p) else { continue };
+            if *c != target {
+                continue;
+            }
+            *c = new.clone();
+            queue.extend(Vec3::ORTHO.into_iter().filter_map(|o| p.apply(o)));
+        }
+        self
+    }
+
+    /// Counts the faces of solid cells that touch a non solid cell or the edge
+    /// of the grid.
+    pub fn surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        self.enumerate()
+            .filter(|(_, c)| is_solid(c))
+            .map(|(p, _)| {
+                Vec3::ORTHO
+                    .into_iter()
+                    .filter(|o| self.get_offset(p, *o).is_none_or(|c| !is_solid(c)))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Counts the faces of solid cells that can be reached from outside of the
+    /// grid, ignoring faces of enclosed air pockets.
+    pub fn exterior_surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        let mut outside = vec![false; self.data.len()];
+        let mut queue = self
+            .enumerate()
+            .filter(|(p, c)| {
+                !is_solid(c)
+                    && (p.x == 0
+                        || p.y == 0
+                        || p.z == 0
+                        || p.x == self.width - 1
+                        || p.y == self.height - 1
+                        || p.z == self.depth - 1)
+            })
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+
+        while let Some(p) = queue.pop() {
+            let i = self.index(p).unwrap();
+            if outside[i] {
+                continue;
+            }
+            outside[i] = true;
+            queue.extend(
+                self.with_offsets(p, Vec3::ORTHO)
+                    .filter(|(_, c)| !is_solid(c))
+                    .map(|(p, _)| p),
+            );
+        }
+
+        self.enumerate()
+            .filter(|(_, c)| is_solid(c))
+            .map(|(p, _)| {
+                Vec3::ORTHO
+                    .into_iter()
+                    .filter(|o| {
+                        p.apply(*o)
+                            .and_then(|n| self.index(n))
+                            .is_none_or(|i| outside[i])
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    #[must_use]
+    pub fn map<F>(self, f: impl Fn(T) -> F) -> Grid3<F> {
+        Grid3 {
+            data: self.data.into_iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+        }
+    }
+}
+
+impl Grid3<bool> {
+    /// Creates a grid just large enough to hold every point, with those points
+    /// set to true.
+    #[must_use]
+    pub fn from_points(points: impl IntoIterator<Item = impl Into<Point3<usize>>>) -> Self {
+        let points = points.into_iter().map(Into::into).collect::<Vec<_>>();
+        let size = points
+            .iter()
+            .fold(Point3::default(), |acc: Point3<usize>, p| {
+                Point3::new(acc.x.max(p.x + 1), acc.y.max(p.y + 1), acc.z.max(p.z + 1))
+            });
+        let mut grid = Self::new_filled(false, size.x, size.y, size.z);
+        grid.apply(points, &true);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the example droplet from 2022 day 18
+    const DROPLET: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    fn droplet() -> Grid3<bool> {
+        Grid3::from_points(DROPLET.lines().map(|l| l.parse::<Point3<usize>>().unwrap()))
+    }
+
+    #[test]
+    fn get_set() {
+        let mut grid = Grid3::new_filled(0, 2, 3, 4);
+        assert_eq!(grid.size(), Point3::new(2, 3, 4));
+        grid.set((1, 2, 3), 5).unwrap();
+        assert_eq!(grid.get((1, 2, 3)), Some(&5));
+        assert_eq!(grid.get((2, 0, 0)), None);
+        assert_eq!(grid.set((0, 3, 0), 1), None);
+        assert_eq!(grid.get_offset((1, 2, 2), Vec3::Z), Some(&5));
+        assert_eq!(
+            grid.find_all(&5).collect::<Vec<_>>(),
+            vec![(1, 2, 3).into()]
+        );
+    }
+
+    #[test]
+    fn from_fn() {
+        let grid = Grid3::from_fn(2, 2, 2, |p| p.x + 2 * p.y + 4 * p.z);
+        assert!(grid.enumerate().all(|(p, c)| p.x + 2 * p.y + 4 * p.z == *c));
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<_>>(),
+            (0..8).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn surface_area() {
+        let grid = Grid3::from_points([(1, 1, 1), (2, 1, 1)]);
+        assert_eq!(grid.surface_area(|c| *c), 10);
+
+        let grid = droplet();
+        assert_eq!(grid.count(&true), 13);
+        assert_eq!(grid.surface_area(|c| *c), 64);
+        assert_eq!(grid.exterior_surface_area(|c| *c), 58);
+    }
+
+    #[test]
+    fn fill() {
+        let mut grid = Grid3::new_filled(0, 3, 3, 3);
+        grid.set((1, 0, 0), 1);
+        grid.set((1, 1, 0), 1);
+        grid.set((1, 2, 0), 1);
+        grid.fill((0, 0, 0), &2);
+        assert_eq!(grid.count(&2), 24);
+        assert_eq!(grid.with_offsets((0, 0, 0), Vec3::ORTHO).count(), 3);
+    }
+}
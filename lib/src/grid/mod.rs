@@ -1,4 +1,8 @@
+mod bit_grid;
+mod grid3;
 mod inner;
 mod outer;
 
+pub use bit_grid::*;
+pub use grid3::*;
 pub use outer::*;
@@ -1,8 +1,9 @@
 use std::{fmt::Display, hash::Hash, vec};
 
 use itertools::Itertools;
+use num::{Num, ToPrimitive};
 
-use crate::{Dir, Entity, IteratorExt, Offset, Point2, point2, tern};
+use crate::{Dir, Entity, IteratorExt, Offset, Point2, Rect, point2, tern};
 
 use super::inner;
 
@@ -81,6 +82,13 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Gets a cell using signed or otherwise non usize coordinates. Returns
+    /// None if the point is negative or outside of the grid.
+    #[must_use]
+    pub fn get_signed<S: Num + Copy + ToPrimitive>(&self, p: impl Into<Point2<S>>) -> Option<&T> {
+        self.get(p.into().cast::<usize>()?)
+    }
+
     #[must_use]
     pub fn get_mut(&mut self, p: impl Into<Point2<usize>>) -> Option<&mut T> {
         let Point2 { x, y } = p.into();
@@ -287,6 +295,34 @@ impl<T> Grid<T> {
         Self(unsafe { inner::InnerGrid::new_unchecked(data) })
     }
 
+    /// Copies the cells inside a rect into a new grid. Returns None if the rect
+    /// isn't entirely inside the grid.
+    #[must_use]
+    pub fn subgrid_rect(&self, rect: Rect<usize>) -> Option<Self>
+    where
+        T: Clone,
+    {
+        self.rect()
+            .contains_rect(rect)
+            .then(|| self.subgrid(rect.min.x, rect.min.y, rect.width(), rect.height()))
+    }
+
+    /// Returns a rect covering every cell of the grid.
+    #[must_use]
+    pub fn rect(&self) -> Rect<usize> {
+        Rect::from_size((0, 0), self.width(), self.height())
+    }
+
+    /// Iterates over the cells inside a rect, row by row. Parts of the rect
+    /// outside of the grid are skipped.
+    pub fn view(&self, rect: Rect<usize>) -> impl Iterator<Item = (Point2<usize>, &T)> + Clone {
+        self.rect()
+            .intersection(rect)
+            .into_iter()
+            .flat_map(|r| r.points())
+            .map(|p| (p, self.get(p).unwrap()))
+    }
+
     pub fn paste(&mut self, other: Self, start_x: usize, start_y: usize) -> Option<()>
     where
         T: Clone,
@@ -639,6 +675,14 @@ ghi",
         assert_eq!(grid.get((2, 2)), Some(&b'i'));
     }
 
+    #[test]
+    fn get_signed() {
+        let grid = Grid::from_chars("ab\ncd").unwrap();
+        assert_eq!(grid.get_signed((1i64, 1)), Some(&'d'));
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.get_signed((0i32, 2)), None);
+    }
+
     #[test]
     fn find() {
         let grid = Grid::from_double_iter_transpose(["abc".chars(), "def".chars(), "ghi".chars()])
@@ -0,0 +1,351 @@
+use std::{error::Error, str::FromStr};
+
+use derive_more::derive::{Add, AddAssign, Display, Neg, Sub, SubAssign};
+
+use crate::{Offset, Point2, Vec2};
+
+/// The six directions of a flat-top hex grid. As an offset, each direction is
+/// a step in axial coordinates where x is q and y is r, so `Point2` and
+/// `Entity` can walk the grid directly.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Display)]
+pub enum HexDir {
+    #[display("North")]
+    North,
+    #[display("NorthEast")]
+    NorthEast,
+    #[display("SouthEast")]
+    SouthEast,
+    #[display("South")]
+    South,
+    #[display("SouthWest")]
+    SouthWest,
+    #[display("NorthWest")]
+    NorthWest,
+}
+
+/// The six directions of a pointy-top hex grid, stepping in the same axial
+/// coordinates as [`HexDir`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Display)]
+pub enum PointyHexDir {
+    #[display("East")]
+    East,
+    #[display("SouthEast")]
+    SouthEast,
+    #[display("SouthWest")]
+    SouthWest,
+    #[display("West")]
+    West,
+    #[display("NorthWest")]
+    NorthWest,
+    #[display("NorthEast")]
+    NorthEast,
+}
+
+/// Implements the shared parts of both hex direction enums. Variants must be
+/// listed in clockwise order, matching the order they are declared in.
+macro_rules! hex_dir {
+    ($name:ident, [$($variant:ident => ($x:expr, $y:expr), $token:literal),*]) => {
+        impl $name {
+            /// Every direction in clockwise order.
+            pub const ALL: [$name; 6] = [$($name::$variant),*];
+
+            /// Returns the index of the direction in [`Self::ALL`], which is
+            /// also the number of clockwise turns from the first direction.
+            #[must_use]
+            #[inline]
+            pub const fn idx(self) -> usize {
+                self as usize
+            }
+
+            const fn from_idx(i: usize) -> Self {
+                Self::ALL[i % 6]
+            }
+        }
+
+        /// Hex directions turn by 60 degrees instead of 90.
+        impl Offset for $name {
+            #[inline]
+            fn reverse(self) -> Self {
+                Self::from_idx(self.idx() + 3)
+            }
+
+            #[inline]
+            fn turn_left(self) -> Self {
+                Self::from_idx(self.idx() + 5)
+            }
+
+            #[inline]
+            fn turn_right(self) -> Self {
+                Self::from_idx(self.idx() + 1)
+            }
+
+            /// No two hex directions are perpendicular.
+            #[inline]
+            fn is_ortho(self, _other: Self) -> bool {
+                false
+            }
+        }
+
+        impl From<$name> for Vec2 {
+            fn from(val: $name) -> Self {
+                match val {
+                    $($name::$variant => Vec2::new($x, $y)),*
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = HexDirParseError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.trim().to_ascii_lowercase().as_str() {
+                    $($token => Ok($name::$variant),)*
+                    _ => Err(HexDirParseError(s.to_string())),
+                }
+            }
+        }
+    };
+}
+
+hex_dir!(HexDir, [
+    North => (0, -1), "n",
+    NorthEast => (1, -1), "ne",
+    SouthEast => (1, 0), "se",
+    South => (0, 1), "s",
+    SouthWest => (-1, 1), "sw",
+    NorthWest => (-1, 0), "nw"
+]);
+
+hex_dir!(PointyHexDir, [
+    East => (1, 0), "e",
+    SouthEast => (0, 1), "se",
+    SouthWest => (-1, 1), "sw",
+    West => (-1, 0), "w",
+    NorthWest => (0, -1), "nw",
+    NorthEast => (1, -1), "ne"
+]);
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("Error parsing HexDir: \"{}\" is not a hex dir", _0)]
+pub struct HexDirParseError(String);
+impl Error for HexDirParseError {}
+
+/// A position on a hex grid in axial coordinates. The third cube coordinate
+/// is implied by q + r + s = 0.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Add,
+    Sub,
+    Neg,
+    AddAssign,
+    SubAssign,
+    Display,
+)]
+#[display("({q}, {r})")]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    /// The center of the grid.
+    pub const ORIGIN: Hex = Hex::new(0, 0);
+
+    /// Creates a hex from its axial coordinates.
+    #[must_use]
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// Creates a hex from cube coordinates, returning None if they don't sum
+    /// to zero.
+    #[must_use]
+    pub const fn from_cube(q: i64, r: i64, s: i64) -> Option<Self> {
+        if q + r + s == 0 {
+            Some(Self::new(q, r))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the implied third cube coordinate.
+    #[must_use]
+    pub const fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// Returns the cube coordinates as a tuple.
+    #[must_use]
+    pub const fn cube(self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    /// Moves the hex by an offset n times.
+    #[must_use]
+    pub fn apply_n(self, dir: impl Offset, n: i64) -> Self {
+        let v: Vec2 = dir.into();
+        Self::new(self.q + v.x as i64 * n, self.r + v.y as i64 * n)
+    }
+
+    /// Moves the hex by an offset.
+    #[must_use]
+    pub fn apply(self, dir: impl Offset) -> Self {
+        self.apply_n(dir, 1)
+    }
+
+    /// Returns the number of steps between two hexes.
+    #[must_use]
+    pub fn dist(self, other: Self) -> u64 {
+        let (dq, dr, ds) = (self - other).cube();
+        dq.unsigned_abs()
+            .max(dr.unsigned_abs())
+            .max(ds.unsigned_abs())
+    }
+
+    /// Returns the number of steps to the origin.
+    #[must_use]
+    pub fn dist_from_origin(self) -> u64 {
+        self.dist(Self::ORIGIN)
+    }
+
+    /// Returns the 6 adjacent hexes.
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> + Clone {
+        HexDir::ALL.into_iter().map(move |d| self.apply(d))
+    }
+
+    /// Iterates over every hex exactly radius steps away, going clockwise
+    /// starting from the hex radius steps in the south west direction.
+    pub fn ring(self, radius: u64) -> impl Iterator<Item = Hex> + Clone {
+        let start = self.apply_n(HexDir::SouthWest, radius as i64);
+        let center = (radius == 0).then_some(self);
+        center.into_iter().chain(
+            HexDir::ALL
+                .into_iter()
+                .flat_map(move |d| std::iter::repeat_n(d, radius as usize))
+                .scan(start, |cur, d| {
+                    let res = *cur;
+                    *cur = cur.apply(d);
+                    Some(res)
+                }),
+        )
+    }
+
+    /// Iterates over every hex at most radius steps away, ring by ring from
+    /// the center outwards.
+    pub fn spiral(self, radius: u64) -> impl Iterator<Item = Hex> + Clone {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+}
+
+impl<T: Into<i64> + num::Num + Copy> From<Point2<T>> for Hex {
+    fn from(value: Point2<T>) -> Self {
+        Self::new(value.x.into(), value.y.into())
+    }
+}
+
+impl From<Hex> for Point2<i64> {
+    fn from(value: Hex) -> Self {
+        Point2::new(value.q, value.r)
+    }
+}
+
+impl From<(i64, i64)> for Hex {
+    fn from((q, r): (i64, i64)) -> Self {
+        Self::new(q, r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::Entity;
+
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(HexDir::North.turn_right(), HexDir::NorthEast);
+        assert_eq!(HexDir::North.turn_left(), HexDir::NorthWest);
+        assert_eq!(HexDir::NorthEast.reverse(), HexDir::SouthWest);
+        assert!(HexDir::South.is_reverse_of(HexDir::North));
+        assert_eq!(PointyHexDir::East.turn_right(), PointyHexDir::SouthEast);
+        assert_eq!(PointyHexDir::West.reverse(), PointyHexDir::East);
+    }
+
+    #[test]
+    fn vectors_sum_to_zero() {
+        let sum = HexDir::ALL
+            .into_iter()
+            .map(Vec2::from)
+            .fold(Vec2::default(), |a, b| a + b);
+        assert_eq!(sum, Vec2::default());
+        for d in HexDir::ALL {
+            assert_eq!(Vec2::from(d) + Vec2::from(d.reverse()), Vec2::default());
+        }
+        for d in PointyHexDir::ALL {
+            assert_eq!(Vec2::from(d) + Vec2::from(d.reverse()), Vec2::default());
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("ne".parse(), Ok(HexDir::NorthEast));
+        assert_eq!(" S ".parse(), Ok(HexDir::South));
+        assert_eq!("w".parse(), Ok(PointyHexDir::West));
+        assert_eq!(
+            "e".parse::<HexDir>(),
+            Err(HexDirParseError("e".to_string()))
+        );
+    }
+
+    #[test]
+    fn dist() {
+        let walk = |s: &str| {
+            s.split(',')
+                .map(|d| d.parse::<HexDir>().unwrap())
+                .fold(Hex::ORIGIN, Hex::apply)
+        };
+        assert_eq!(walk("ne,ne,ne").dist_from_origin(), 3);
+        assert_eq!(walk("ne,ne,sw,sw").dist_from_origin(), 0);
+        assert_eq!(walk("ne,ne,s,s").dist_from_origin(), 2);
+        assert_eq!(walk("se,sw,se,sw,sw").dist_from_origin(), 3);
+        assert_eq!(Hex::from_cube(1, 2, -3), Some(Hex::new(1, 2)));
+        assert_eq!(Hex::from_cube(1, 2, 3), None);
+    }
+
+    #[test]
+    fn entity() {
+        let en = Entity::new((0i64, 0i64), HexDir::North)
+            .step()
+            .unwrap()
+            .turn_right()
+            .step()
+            .unwrap();
+        assert_eq!(Hex::from(en.pos()), Hex::new(1, -2));
+        assert_eq!(Hex::from(en.pos()).dist_from_origin(), 2);
+    }
+
+    #[test]
+    fn rings() {
+        let center = Hex::new(2, -1);
+        assert_eq!(center.ring(0).collect_vec(), vec![center]);
+        for radius in 1..5 {
+            let ring = center.ring(radius).collect_vec();
+            assert_eq!(ring.len() as u64, 6 * radius);
+            assert!(ring.iter().all_unique());
+            assert!(ring.iter().all(|h| h.dist(center) == radius));
+        }
+        let spiral = center.spiral(3).collect_vec();
+        assert_eq!(spiral.len(), 37);
+        assert!(spiral.iter().all_unique());
+        assert!(center.neighbors().all(|n| n.dist(center) == 1));
+    }
+}
@@ -0,0 +1,329 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use indexmap::IndexSet;
+
+use crate::Grid;
+
+use super::{Rgb, render};
+
+/// The largest code the gif flavor of lzw can use.
+const MAX_CODES: u16 = 4096;
+
+/// Collects frames of a simulation and encodes them as a looping animated gif.
+/// Every frame must have the same dimensions, and the whole animation can use
+/// at most 256 distinct colors.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    scale: usize,
+    delay: u16,
+    size: Option<(usize, usize)>,
+    frames: Vec<Vec<Rgb>>,
+}
+
+impl Animation {
+    /// Creates an empty animation where each cell is drawn as a scale x scale
+    /// square and each frame is shown for delay hundredths of a second.
+    #[must_use]
+    pub fn new(scale: usize, delay: u16) -> Self {
+        assert!(scale > 0, "Image scale must be at least 1");
+        Self {
+            scale,
+            delay,
+            size: None,
+            frames: vec![],
+        }
+    }
+
+    /// Returns the number of frames recorded.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether no frames have been recorded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Records a grid as the next frame, mapping each cell to a color. Panics
+    /// if the grid has a different size than the previous frames.
+    pub fn push_frame<T>(&mut self, grid: &Grid<T>, color: impl Fn(&T) -> Rgb) -> &mut Self {
+        let (width, height, pixels) = render(grid, self.scale, color);
+        let size = *self.size.get_or_insert((width, height));
+        assert_eq!(
+            size,
+            (width, height),
+            "Every frame of an animation must have the same size"
+        );
+        self.frames.push(pixels);
+        self
+    }
+
+    /// Encodes the recorded frames as a gif. Returns an error if the frames
+    /// use more than 256 colors.
+    pub fn to_gif(&self) -> io::Result<Vec<u8>> {
+        let (width, height) = self.size.unwrap_or((0, 0));
+
+        let palette: IndexSet<Rgb> = self.frames.iter().flatten().copied().collect();
+        if palette.len() > 256 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("gif can only hold 256 colors, found {}", palette.len()),
+            ));
+        }
+
+        // number of bits needed to index the palette
+        let depth = (usize::BITS - palette.len().saturating_sub(1).leading_zeros()).max(1);
+
+        let mut out = b"GIF89a".to_vec();
+        out.extend((width as u16).to_le_bytes());
+        out.extend((height as u16).to_le_bytes());
+        // global color table present, 8 bit color resolution, table size
+        out.push(0x80 | 0x70 | (depth - 1) as u8);
+        out.extend([0, 0]);
+        for i in 0..1 << depth {
+            let c = palette.get_index(i).copied().unwrap_or_default();
+            out.extend(c.to_array());
+        }
+
+        // loop forever
+        out.extend([0x21, 0xff, 0x0b]);
+        out.extend(b"NETSCAPE2.0");
+        out.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        let min_code_size = depth.max(2) as u8;
+        for frame in &self.frames {
+            // graphic control extension with the frame delay
+            out.extend([0x21, 0xf9, 0x04, 0x00]);
+            out.extend(self.delay.to_le_bytes());
+            out.extend([0x00, 0x00]);
+
+            // image descriptor covering the whole screen
+            out.push(0x2c);
+            out.extend([0, 0, 0, 0]);
+            out.extend((width as u16).to_le_bytes());
+            out.extend((height as u16).to_le_bytes());
+            out.push(0);
+
+            let indices = frame
+                .iter()
+                .map(|c| palette.get_index_of(c).unwrap() as u8)
+                .collect::<Vec<_>>();
+            out.push(min_code_size);
+            for block in lzw_encode(&indices, min_code_size).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend(block);
+            }
+            out.push(0);
+        }
+
+        out.push(0x3b);
+        Ok(out)
+    }
+
+    /// Writes the recorded frames to a file as a gif. See
+    /// [`Animation::to_gif`].
+    pub fn write_gif(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_gif()?)
+    }
+}
+
+/// Packs variable width codes into bytes, least significant bit first.
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.acc |= u32::from(code) << self.bits;
+        self.bits += u32::from(size);
+        while self.bits >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+/// Compresses palette indices with the variable width lzw used by gif.
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter {
+        out: vec![],
+        acc: 0,
+        bits: 0,
+    };
+    let mut dict: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end + 1;
+    let mut size = min_code_size + 1;
+
+    writer.write(clear, size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(end, size);
+        return writer.finish();
+    };
+
+    let mut prefix = u16::from(first);
+    for &k in rest {
+        if let Some(&code) = dict.get(&(prefix, k)) {
+            prefix = code;
+            continue;
+        }
+
+        writer.write(prefix, size);
+        // the decoder lags one code behind, so widen once it has filled the
+        // current code size
+        if next_code >= 1 << size && size < 12 {
+            size += 1;
+        }
+        if next_code < MAX_CODES {
+            dict.insert((prefix, k), next_code);
+            next_code += 1;
+        } else {
+            writer.write(clear, size);
+            dict.clear();
+            next_code = end + 1;
+            size = min_code_size + 1;
+        }
+        prefix = u16::from(k);
+    }
+
+    writer.write(prefix, size);
+    if next_code >= 1 << size && size < 12 {
+        size += 1;
+    }
+    writer.write(end, size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A straightforward gif lzw decoder used to check the encoder round trips.
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+
+        let mut pos = 0;
+        let mut read = |size: u8| {
+            let mut code = 0u16;
+            for i in 0..size {
+                let bit = (data[pos / 8] >> (pos % 8)) & 1;
+                code |= u16::from(bit) << i;
+                pos += 1;
+            }
+            code
+        };
+
+        let reset = || -> Vec<Vec<u8>> {
+            (0..clear)
+                .map(|i| vec![i as u8])
+                .chain([vec![], vec![]])
+                .collect()
+        };
+        let mut table = reset();
+        let mut size = min_code_size + 1;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut out = vec![];
+
+        loop {
+            let code = read(size);
+            if code == clear {
+                table = reset();
+                size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+            let entry = match (table.get(code as usize), &prev) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => {
+                    let mut e = p.clone();
+                    e.push(p[0]);
+                    e
+                }
+                (None, None) => panic!("invalid first code"),
+            };
+            out.extend(&entry);
+            if let Some(mut p) = prev
+                && table.len() < MAX_CODES as usize
+            {
+                p.push(entry[0]);
+                table.push(p);
+                if table.len() >= 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            prev = Some(entry);
+        }
+        out
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let small = [0, 1, 1, 1, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1];
+        assert_eq!(lzw_decode(&lzw_encode(&small, 2), 2), small);
+
+        assert_eq!(lzw_decode(&lzw_encode(&[], 2), 2), Vec::<u8>::new());
+
+        // enough varied data to fill the dictionary and force a clear code
+        let mut state = 12345u32;
+        let big = (0..40000)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as u8 % 16
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lzw_decode(&lzw_encode(&big, 4), 4), big);
+    }
+
+    #[test]
+    fn animation() {
+        let mut anim = Animation::new(1, 10);
+        assert!(anim.is_empty());
+
+        let mut grid = Grid::new_filled(false, 3, 2);
+        for x in 0..3 {
+            grid.set((x, 0), true);
+            anim.push_frame(&grid, |c| if *c { Rgb::WHITE } else { Rgb::BLACK });
+        }
+        assert_eq!(anim.len(), 3);
+
+        let gif = anim.to_gif().unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], &[3, 0, 2, 0]);
+        assert_eq!(gif[10], 0xf0);
+        assert_eq!(gif.last(), Some(&0x3b));
+        // one graphic control extension per frame
+        assert_eq!(
+            gif.windows(3).filter(|w| w == &[0x21, 0xf9, 0x04]).count(),
+            3
+        );
+    }
+
+    #[test]
+    fn too_many_colors() {
+        let grid = Grid::from_double_iter([0..=255u8, 0..=255u8]).unwrap();
+        let mut anim = Animation::new(1, 10);
+        anim.push_frame(&grid, |&c| Rgb::gray(c));
+        assert!(anim.to_gif().is_ok());
+        anim.push_frame(&grid, |&c| Rgb::new(c, 0, 0));
+        assert!(anim.to_gif().is_err());
+    }
+}
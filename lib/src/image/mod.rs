@@ -0,0 +1,137 @@
+mod gif;
+mod png;
+
+use std::{fs, io, path::Path};
+
+use crate::Grid;
+
+pub use gif::*;
+
+/// A 24 bit color used when exporting grids as images.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const GREEN: Rgb = Rgb(0, 255, 0);
+    pub const BLUE: Rgb = Rgb(0, 0, 255);
+
+    /// Creates a color from its red, green and blue components.
+    #[must_use]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self(r, g, b)
+    }
+
+    /// Creates a gray color with all components set to v.
+    #[must_use]
+    pub const fn gray(v: u8) -> Self {
+        Self(v, v, v)
+    }
+
+    /// Returns the components as an array in rgb order.
+    #[must_use]
+    pub const fn to_array(self) -> [u8; 3] {
+        [self.0, self.1, self.2]
+    }
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self(r, g, b)
+    }
+}
+
+/// Renders a grid into a row major list of pixels, where every cell becomes a
+/// scale x scale square. Returns the width and height of the image in pixels.
+fn render<T>(grid: &Grid<T>, scale: usize, color: impl Fn(&T) -> Rgb) -> (usize, usize, Vec<Rgb>) {
+    assert!(scale > 0, "Image scale must be at least 1");
+    let width = grid.width() * scale;
+    let height = grid.height() * scale;
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in grid.rows() {
+        let start = pixels.len();
+        for cell in row {
+            let c = color(cell);
+            pixels.extend(std::iter::repeat_n(c, scale));
+        }
+        for _ in 1..scale {
+            pixels.extend_from_within(start..start + width);
+        }
+    }
+    (width, height, pixels)
+}
+
+impl<T> Grid<T> {
+    /// Encodes the grid as a binary PPM (P6) image. Each cell is mapped to a
+    /// color with the given closure and drawn as a scale x scale square.
+    pub fn to_ppm(&self, scale: usize, color: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        let (width, height, pixels) = render(self, scale, color);
+        let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+        out.extend(pixels.into_iter().flat_map(Rgb::to_array));
+        out
+    }
+
+    /// Encodes the grid as a PNG image. Each cell is mapped to a color with the
+    /// given closure and drawn as a scale x scale square.
+    pub fn to_png(&self, scale: usize, color: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        let (width, height, pixels) = render(self, scale, color);
+        png::encode(width, height, &pixels)
+    }
+
+    /// Writes the grid to a file as a PPM image. See [`Grid::to_ppm`].
+    pub fn write_ppm(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        fs::write(path, self.to_ppm(scale, color))
+    }
+
+    /// Writes the grid to a file as a PNG image. See [`Grid::to_png`].
+    pub fn write_png(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        fs::write(path, self.to_png(scale, color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Grid<u8> {
+        Grid::new(vec![vec![255, 0], vec![0, 255]]).unwrap()
+    }
+
+    #[test]
+    fn render_scaled() {
+        let (width, height, pixels) = render(&checkerboard(), 2, |&c| Rgb::gray(c));
+        assert_eq!((width, height), (4, 4));
+        let w = Rgb::WHITE;
+        let b = Rgb::BLACK;
+        assert_eq!(pixels, vec![w, w, b, b, w, w, b, b, b, b, w, w, b, b, w, w]);
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = checkerboard().to_ppm(1, |&c| Rgb::gray(c));
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(
+            &ppm[header.len()..],
+            &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn gray() {
+        assert_eq!(Rgb::gray(7), Rgb(7, 7, 7));
+        assert_eq!(Rgb::from((1, 2, 3)).to_array(), [1, 2, 3]);
+    }
+}
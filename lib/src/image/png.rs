@@ -0,0 +1,121 @@
+use super::Rgb;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// The largest amount of data a single stored deflate block can hold.
+const MAX_STORED: usize = 0xffff;
+
+/// Calculates the crc32 of some bytes, as used by png chunks.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Calculates the adler32 checksum of some bytes, as used by zlib.
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/// Wraps data in a zlib stream made of uncompressed deflate blocks. Images from
+/// puzzles are small enough that compression isn't worth a dependency.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_STORED * 5 + 11);
+    out.extend([0x78, 0x01]);
+
+    let mut chunks = data.chunks(MAX_STORED).peekable();
+    if chunks.peek().is_none() {
+        out.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let len = chunk.len() as u16;
+        out.push(u8::from(chunks.peek().is_none()));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(chunk);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Encodes row major pixels as an 8 bit truecolor png.
+pub fn encode(width: usize, height: usize, pixels: &[Rgb]) -> Vec<u8> {
+    debug_assert_eq!(pixels.len(), width * height);
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // bit depth 8, color type rgb, default compression, filter and interlace
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for row in pixels.chunks(width.max(1)) {
+        // every scanline starts with its filter type, which is always none
+        raw.push(0);
+        raw.extend(row.iter().flat_map(|p| p.to_array()));
+    }
+
+    let mut out = SIGNATURE.to_vec();
+    write_chunk(&mut out, *b"IHDR", &header);
+    write_chunk(&mut out, *b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut out, *b"IEND", &[]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn stored_blocks() {
+        let data = vec![7; MAX_STORED + 10];
+        let z = zlib_stored(&data);
+        // header, two block headers, data and checksum
+        assert_eq!(z.len(), 2 + 5 + 5 + data.len() + 4);
+        assert_eq!(&z[2..7], &[0x00, 0xff, 0xff, 0x00, 0x00]);
+        assert_eq!(
+            &z[7 + MAX_STORED..12 + MAX_STORED],
+            &[0x01, 10, 0, !10, 0xff]
+        );
+    }
+
+    #[test]
+    fn encode_png() {
+        let png = encode(2, 1, &[Rgb::RED, Rgb::BLUE]);
+        assert_eq!(&png[..8], &SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..20], &2u32.to_be_bytes());
+        assert_eq!(&png[20..24], &1u32.to_be_bytes());
+        assert_eq!(
+            &png[png.len() - 8..],
+            &[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+}
@@ -1,23 +1,45 @@
 mod a_star;
+mod body;
 mod digit_iter;
 mod entity;
+mod graph;
 mod grid;
+mod hex;
+mod image;
 mod offset;
 mod point2;
+mod point3;
+mod polygon;
 mod range;
 mod range_set;
+mod rect;
+mod segment;
+mod spiral;
+mod transform;
 mod utils;
+mod walker;
 
 pub use crate::a_star::*;
+pub use crate::body::*;
 pub use crate::digit_iter::*;
 pub use crate::entity::*;
+pub use crate::graph::*;
 pub use crate::grid::*;
+pub use crate::hex::*;
+pub use crate::image::*;
 pub use crate::md5::*;
 pub use crate::offset::*;
 pub use crate::point2::*;
+pub use crate::point3::*;
+pub use crate::polygon::*;
 pub use crate::range::*;
 pub use crate::range_set::*;
+pub use crate::rect::*;
+pub use crate::segment::*;
+pub use crate::spiral::*;
+pub use crate::transform::*;
 pub use crate::utils::*;
+pub use crate::walker::*;
 pub use derive_more;
 pub use indexmap;
 pub use itertools;
@@ -0,0 +1,279 @@
+use std::{error::Error, fmt::Debug, hash::Hash, str::FromStr};
+
+use derive_more::derive::Display;
+
+use crate::{Dir, DirConvertError, Offset, Vec2};
+
+/// An enum containing the orthogonal and diagonal directions in a 2d plane.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Display)]
+pub enum Dir8 {
+    #[display("North")]
+    North,
+    #[display("NorthEast")]
+    NorthEast,
+    #[display("East")]
+    East,
+    #[display("SouthEast")]
+    SouthEast,
+    #[display("South")]
+    South,
+    #[display("SouthWest")]
+    SouthWest,
+    #[display("West")]
+    West,
+    #[display("NorthWest")]
+    NorthWest,
+}
+
+impl Dir8 {
+    /// Every direction in clockwise order, starting from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    /// Each of the cardinal directions in clockwise order.
+    pub const ORTHO: [Dir8; 4] = [Dir8::North, Dir8::East, Dir8::South, Dir8::West];
+
+    /// Each of the diagonal directions in clockwise order.
+    pub const CORNERS: [Dir8; 4] = [
+        Dir8::NorthEast,
+        Dir8::SouthEast,
+        Dir8::SouthWest,
+        Dir8::NorthWest,
+    ];
+
+    /// Returns the index of the direction in [`Dir8::ALL`], so that dirs can be
+    /// used as the keys for an array.
+    #[must_use]
+    #[inline]
+    pub const fn idx(self) -> usize {
+        self as usize
+    }
+
+    const fn from_idx(i: usize) -> Self {
+        Self::ALL[i % 8]
+    }
+
+    /// Turns left (ccw) by 45 degrees.
+    #[must_use]
+    #[inline]
+    pub const fn turn_left_45(self) -> Self {
+        Self::from_idx(self.idx() + 7)
+    }
+
+    /// Turns right (cw) by 45 degrees.
+    #[must_use]
+    #[inline]
+    pub const fn turn_right_45(self) -> Self {
+        Self::from_idx(self.idx() + 1)
+    }
+
+    /// Returns whether the direction is diagonal.
+    #[must_use]
+    #[inline]
+    pub const fn is_diagonal(self) -> bool {
+        self.idx() % 2 == 1
+    }
+}
+
+impl Offset for Dir8 {
+    #[inline]
+    fn reverse(self) -> Self {
+        Self::from_idx(self.idx() + 4)
+    }
+
+    #[inline]
+    fn turn_left(self) -> Self {
+        Self::from_idx(self.idx() + 6)
+    }
+
+    #[inline]
+    fn turn_right(self) -> Self {
+        Self::from_idx(self.idx() + 2)
+    }
+
+    #[inline]
+    fn is_ortho(self, other: Self) -> bool {
+        self.idx().abs_diff(other.idx()) % 4 == 2
+    }
+}
+
+impl From<Dir8> for Vec2 {
+    fn from(val: Dir8) -> Self {
+        match val {
+            Dir8::North => Vec2::new(0, -1),
+            Dir8::NorthEast => Vec2::new(1, -1),
+            Dir8::East => Vec2::new(1, 0),
+            Dir8::SouthEast => Vec2::new(1, 1),
+            Dir8::South => Vec2::new(0, 1),
+            Dir8::SouthWest => Vec2::new(-1, 1),
+            Dir8::West => Vec2::new(-1, 0),
+            Dir8::NorthWest => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(value: Dir) -> Self {
+        match value {
+            Dir::East => Dir8::East,
+            Dir::West => Dir8::West,
+            Dir::North => Dir8::North,
+            Dir::South => Dir8::South,
+        }
+    }
+}
+
+impl TryFrom<Dir8> for Dir {
+    type Error = DirConvertError;
+    fn try_from(value: Dir8) -> Result<Self, Self::Error> {
+        Dir::try_from(Vec2::from(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("Error converting to Dir8: \"{}\" is not a unit vector", _0)]
+pub struct Dir8ConvertError(Vec2);
+impl Error for Dir8ConvertError {}
+
+impl TryFrom<Vec2> for Dir8 {
+    type Error = Dir8ConvertError;
+    fn try_from(value: Vec2) -> Result<Self, Self::Error> {
+        Dir8::ALL
+            .into_iter()
+            .find(|d| Vec2::from(*d) == value)
+            .ok_or(Dir8ConvertError(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("Error parsing Dir8: \"{}\" is not a dir", _0)]
+pub struct Dir8ParseError(String);
+impl Error for Dir8ParseError {}
+
+impl TryFrom<char> for Dir8 {
+    type Error = Dir8ParseError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Dir::try_from(value)
+            .map(Dir8::from)
+            .map_err(|_| Dir8ParseError(value.to_string()))
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = Dir8ParseError;
+
+    /// Parses a single direction like Dir does, or a vertical and horizontal
+    /// direction joined together, such as "NE", "up-left" or "south west".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || Dir8ParseError(s.to_string());
+        if let Ok(dir) = s.parse::<Dir>() {
+            return Ok(dir.into());
+        }
+
+        let lower = s.to_ascii_lowercase();
+        let split = ["-", "_", " "]
+            .into_iter()
+            .find_map(|sep| lower.split_once(sep))
+            .or_else(|| {
+                ["north", "south", "up", "down", "n", "s", "u", "d"]
+                    .into_iter()
+                    .find_map(|v| lower.strip_prefix(v).map(|h| (v, h)))
+            });
+        let (vertical, horizontal) = split.ok_or_else(err)?;
+
+        let vertical = vertical.parse::<Dir>().map_err(|_| err())?;
+        let horizontal = horizontal.parse::<Dir>().map_err(|_| err())?;
+        if vertical.is_ortho(Dir::East) && horizontal.is_ortho(Dir::North) {
+            Dir8::try_from(Vec2::from(vertical) + Vec2::from(horizontal)).map_err(|_| err())
+        } else {
+            Err(err())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::{Entity, Point2};
+
+    use super::*;
+
+    #[test]
+    fn index() {
+        assert!(Dir8::ALL.into_iter().map(Dir8::idx).all_unique());
+        assert!(Dir8::CORNERS.into_iter().all(Dir8::is_diagonal));
+        assert!(!Dir8::ORTHO.into_iter().any(Dir8::is_diagonal));
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir8::North.turn_right(), Dir8::East);
+        assert_eq!(Dir8::North.turn_left(), Dir8::West);
+        assert_eq!(Dir8::NorthEast.turn_right(), Dir8::SouthEast);
+        assert_eq!(Dir8::North.turn_right_45(), Dir8::NorthEast);
+        assert_eq!(Dir8::North.turn_left_45(), Dir8::NorthWest);
+        assert_eq!(Dir8::NorthEast.reverse(), Dir8::SouthWest);
+        assert!(Dir8::NorthEast.is_ortho(Dir8::NorthWest));
+        assert!(!Dir8::NorthEast.is_ortho(Dir8::North));
+
+        for d in Dir8::ALL {
+            let v = Vec2::from(d);
+            assert_eq!(Vec2::from(d.turn_left()), v.turn_left());
+            assert_eq!(Vec2::from(d.turn_right()), v.turn_right());
+            assert_eq!(Vec2::from(d.reverse()), v.reverse());
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        for d in Dir::ORTHO {
+            assert_eq!(Dir::try_from(Dir8::from(d)), Ok(d));
+            assert_eq!(Vec2::from(Dir8::from(d)), Vec2::from(d));
+        }
+        assert!(Dir::try_from(Dir8::SouthWest).is_err());
+        assert_eq!(Dir8::try_from(Vec2::new(1, 1)), Ok(Dir8::SouthEast));
+        assert_eq!(
+            Dir8::try_from(Vec2::new(2, 0)),
+            Err(Dir8ConvertError(Vec2::new(2, 0)))
+        );
+        assert_eq!(
+            Dir8::ALL
+                .map(Vec2::from)
+                .into_iter()
+                .sorted_by_key(|v| (v.y, v.x))
+                .collect_vec(),
+            Vec2::SURROUNDING.to_vec()
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("NE".parse(), Ok(Dir8::NorthEast));
+        assert_eq!("up-left".parse(), Ok(Dir8::NorthWest));
+        assert_eq!("South West".parse(), Ok(Dir8::SouthWest));
+        assert_eq!("southeast".parse(), Ok(Dir8::SouthEast));
+        assert_eq!("dr".parse(), Ok(Dir8::SouthEast));
+        assert_eq!("north".parse(), Ok(Dir8::North));
+        assert_eq!("<".parse(), Ok(Dir8::West));
+        assert_eq!(Dir8::try_from('^'), Ok(Dir8::North));
+        assert_eq!("ew".parse::<Dir8>(), Err(Dir8ParseError("ew".to_string())));
+        assert!("up-down".parse::<Dir8>().is_err());
+        assert!("left-up".parse::<Dir8>().is_err());
+    }
+
+    #[test]
+    fn entity() {
+        let en = Entity::new((0, 0), Dir8::SouthEast).step().unwrap();
+        assert_eq!(en.pos(), Point2::new(1, 1));
+        assert_eq!(en.turn_left().step().unwrap().pos(), Point2::new(2, 0));
+    }
+}
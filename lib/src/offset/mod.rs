@@ -1,10 +1,14 @@
 mod dir;
+mod dir8;
 mod vec2;
+mod vec3;
 
 use std::{fmt::Display, hash::Hash};
 
 pub use dir::*;
+pub use dir8::*;
 pub use vec2::*;
+pub use vec3::*;
 
 /// Represents any object that gives an offset from a position without depending
 /// on the position.
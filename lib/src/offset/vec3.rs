@@ -0,0 +1,193 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use derive_more::derive::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use num::Num;
+
+use crate::{Point3, Point3ParseError};
+
+/// An arbitrary 3d vector that has an x, y and z component.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    Neg,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+)]
+pub struct Vec3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// Builds every offset with each component in -1..=1, skipping zero, in the
+/// order they are encountered in a linear search.
+const fn surrounding() -> [Vec3; 26] {
+    let mut res = [Vec3::new(0, 0, 0); 26];
+    let mut i = 0;
+    let mut n = 0;
+    while n < 27 {
+        let v = Vec3::new(n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+        if n != 13 {
+            res[i] = v;
+            i += 1;
+        }
+        n += 1;
+    }
+    res
+}
+
+impl Vec3 {
+    /// The unit vector along the positive x axis.
+    pub const X: Vec3 = Vec3::new(1, 0, 0);
+
+    /// The unit vector along the positive y axis.
+    pub const Y: Vec3 = Vec3::new(0, 1, 0);
+
+    /// The unit vector along the positive z axis.
+    pub const Z: Vec3 = Vec3::new(0, 0, 1);
+
+    /// The six face neighbours of a cube.
+    pub const ORTHO: [Vec3; 6] = [
+        Vec3::new(1, 0, 0),
+        Vec3::new(-1, 0, 0),
+        Vec3::new(0, 1, 0),
+        Vec3::new(0, -1, 0),
+        Vec3::new(0, 0, 1),
+        Vec3::new(0, 0, -1),
+    ];
+
+    /// All 26 adjacent unit offsets, including edges and corners.
+    pub const SURROUNDING: [Vec3; 26] = surrounding();
+
+    /// Creates a Vec3 with the given x, y and z components.
+    #[must_use]
+    #[inline]
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Reverses the vector.
+    #[must_use]
+    #[inline]
+    pub const fn reverse(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+
+    /// Calculates the dot product between two Vec3s.
+    #[must_use]
+    #[inline]
+    pub const fn dot(self, other: Self) -> isize {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Calculates the cross product between two Vec3s.
+    #[must_use]
+    pub const fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Returns the manhattan length of the vector.
+    #[must_use]
+    pub const fn manhattan_len(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs() + self.z.unsigned_abs()
+    }
+
+    /// Calculates b - a, or the vector staring at a and pointing to b, as a
+    /// Vec3.
+    #[must_use]
+    pub fn between<T: Num + Copy + TryInto<isize>>(a: Point3<T>, b: Point3<T>) -> Option<Self> {
+        Some(Self {
+            x: b.x.try_into().ok()? - a.x.try_into().ok()?,
+            y: b.y.try_into().ok()? - a.y.try_into().ok()?,
+            z: b.z.try_into().ok()? - a.z.try_into().ok()?,
+        })
+    }
+}
+
+impl Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Num + Copy + TryInto<isize>> TryFrom<Point3<T>> for Vec3 {
+    type Error = <T as TryInto<isize>>::Error;
+
+    fn try_from(value: Point3<T>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            x: value.x.try_into()?,
+            y: value.y.try_into()?,
+            z: value.z.try_into()?,
+        })
+    }
+}
+
+impl FromStr for Vec3 {
+    type Err = Point3ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Point3<isize>>()
+            .map(|p| Self::new(p.x, p.y, p.z))
+            .map_err(|_| Point3ParseError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Vec3::new(1, 2, 3) + Vec3::new(1, -1, 0), Vec3::new(2, 1, 3));
+        assert_eq!(Vec3::new(1, -2, 3) * 2, Vec3::new(2, -4, 6));
+        assert_eq!(-Vec3::new(1, -2, 3), Vec3::new(1, -2, 3).reverse());
+    }
+
+    #[test]
+    fn products() {
+        assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+        assert_eq!(Vec3::new(1, 2, 3).dot(Vec3::new(4, -5, 6)), 12);
+        assert_eq!(Vec3::new(1, -2, 3).manhattan_len(), 6);
+    }
+
+    #[test]
+    fn neighbours() {
+        assert!(Vec3::SURROUNDING.iter().all_unique());
+        assert!(!Vec3::SURROUNDING.contains(&Vec3::default()));
+        assert_eq!(Vec3::SURROUNDING[0], Vec3::new(-1, -1, -1));
+        assert!(Vec3::ORTHO.iter().all(|v| v.manhattan_len() == 1));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("<1,-2, 3>".parse(), Ok(Vec3::new(1, -2, 3)));
+        assert!("1,2".parse::<Vec3>().is_err());
+    }
+
+    #[test]
+    fn between() {
+        assert_eq!(
+            Vec3::between(Point3::new(1, 1, 1), Point3::new(4, 6, 0)),
+            Some(Vec3::new(3, 5, -1))
+        );
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 use derive_more::derive::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
-use num::{CheckedAdd, CheckedSub, Float, Num};
+use num::{CheckedAdd, CheckedSub, Float, Num, Signed, ToPrimitive, cast};
 
 use crate::{Offset, Vec2, abs_diff, tern};
 
@@ -126,6 +126,45 @@ impl<T: Num + Copy> Point2<T> {
         Some(self)
     }
 
+    /// Applies a dir to a point with signed coordinates. Signed points can go
+    /// below zero, so unlike [`Point2::apply`] no Option is needed.
+    #[must_use]
+    pub fn apply_signed(self, dir: impl Into<Vec2>) -> Self
+    where
+        T: Signed + num::NumCast,
+    {
+        self.apply_n_signed(dir, T::one())
+    }
+
+    /// Applies a dir scaled by a factor to a point with signed coordinates.
+    #[must_use]
+    pub fn apply_n_signed(self, dir: impl Into<Vec2>, n: T) -> Self
+    where
+        T: Signed + num::NumCast,
+    {
+        let dir: Vec2 = dir.into();
+        let scaled = |v: isize| -> T {
+            cast::<isize, T>(v).expect("offset doesn't fit in the coordinate type") * n
+        };
+        Self {
+            x: self.x + scaled(dir.x),
+            y: self.y + scaled(dir.y),
+        }
+    }
+
+    /// Converts the point to another coordinate type, such as `Point2<i64>`
+    /// to `Point2<usize>` for grid lookups. Returns None if either coordinate
+    /// doesn't fit, such as a negative usize.
+    pub fn cast<U: Num + Copy + num::NumCast>(self) -> Option<Point2<U>>
+    where
+        T: ToPrimitive,
+    {
+        Some(Point2::new(
+            <U as num::NumCast>::from(self.x)?,
+            <U as num::NumCast>::from(self.y)?,
+        ))
+    }
+
     // Checks if a point is in [a, b) for both coordinates.
     pub fn within(&self, a: Self, b: Self) -> bool
     where
@@ -304,6 +343,17 @@ mod tests {
         assert_eq!(Point2::new(1, 2).manhattan_dist((3, 4).into()), 4);
     }
 
+    #[test]
+    fn signed() {
+        let p = Point2::new(0i64, 0);
+        assert_eq!(p.apply_signed(Dir::North), (0, -1).into());
+        assert_eq!(p.apply_n_signed(Dir::West, 3), (-3, 0).into());
+        assert_eq!(Point2::new(-1i32, 2).cast::<usize>(), None);
+        assert_eq!(Point2::new(1i32, 2).cast::<usize>(), Some((1, 2).into()));
+        assert_eq!(Point2::new(1usize, 2).cast::<i64>(), Some((1, 2).into()));
+        assert_eq!(Point2::new(300i64, 0).cast::<u8>(), None);
+    }
+
     #[test]
     fn reflect() {
         let point = Point2::new(1, 2);
@@ -0,0 +1,296 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Add, Neg, Sub},
+    str::FromStr,
+};
+
+use derive_more::derive::{
+    Add, AddAssign, Display as DeriveDisplay, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign,
+    Sub, SubAssign,
+};
+use itertools::Itertools;
+use num::{CheckedAdd, CheckedSub, Float, Num};
+
+use crate::{Vec3, abs_diff, tern};
+
+/// Represents a point in 3d space.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Hash,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Neg,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    RemAssign,
+)]
+pub struct Point3<T: Num + Copy> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Num + Copy> Point3<T> {
+    /// Creates a point from an x, y and z coordinate.
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Creates a point from a tuple.
+    pub const fn from_tuple((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the x, y and z coordinates as a tuple.
+    pub const fn into_tuple(self) -> (T, T, T) {
+        (self.x, self.y, self.z)
+    }
+
+    /// Applies an offset to the point. Returns None if any coordinate
+    /// overflows.
+    pub fn apply(self, offset: Vec3) -> Option<Self>
+    where
+        T: CheckedAdd + CheckedSub + TryFrom<isize>,
+    {
+        self.apply_n(offset, T::one())
+    }
+
+    /// Applies an offset scaled by a factor to the point.
+    pub fn apply_n(self, offset: Vec3, n: T) -> Option<Self>
+    where
+        T: CheckedAdd + CheckedSub + TryFrom<isize>,
+    {
+        fn axis<T: Num + Copy + CheckedAdd + CheckedSub + TryFrom<isize>>(
+            a: T,
+            d: isize,
+            n: T,
+        ) -> Option<T> {
+            let op = tern!(d < 0, T::checked_sub, T::checked_add);
+            let new = T::one() * d.abs().try_into().ok()? * n;
+            op(&a, &new)
+        }
+
+        Some(Self {
+            x: axis(self.x, offset.x, n)?,
+            y: axis(self.y, offset.y, n)?,
+            z: axis(self.z, offset.z, n)?,
+        })
+    }
+
+    /// Checks if a point is in [a, b) for all coordinates.
+    pub fn within(&self, a: Self, b: Self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.x >= a.x
+            && self.y >= a.y
+            && self.z >= a.z
+            && self.x < b.x
+            && self.y < b.y
+            && self.z < b.z
+    }
+
+    /// Applies a mapping function to each coordinate independently.
+    pub fn map<F: Num + Copy>(self, f: impl Fn(T) -> F) -> Point3<F> {
+        Point3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    /// Calculates the distance squared between the point and another point.
+    pub fn dist_squared(&self, other: Self) -> T
+    where
+        T: PartialOrd,
+    {
+        let dx = abs_diff(other.x, self.x);
+        let dy = abs_diff(other.y, self.y);
+        let dz = abs_diff(other.z, self.z);
+        dx * dx + dy * dy + dz * dz
+    }
+
+    /// Calculates the distance between the point and another point.
+    pub fn dist(&self, other: Self) -> T
+    where
+        T: Float + PartialOrd,
+    {
+        self.dist_squared(other).sqrt()
+    }
+
+    /// Calculates the manhattan distance between the point and another point
+    pub fn manhattan_dist(&self, other: Self) -> T
+    where
+        T: PartialOrd,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+}
+
+impl<T: Num + Copy> Add<T> for Point3<T> {
+    type Output = Point3<T>;
+    fn add(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x + rhs,
+            y: self.y + rhs,
+            z: self.z + rhs,
+        }
+    }
+}
+
+impl<T: Num + Copy> Sub<T> for Point3<T> {
+    type Output = Point3<T>;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x - rhs,
+            y: self.y - rhs,
+            z: self.z - rhs,
+        }
+    }
+}
+
+impl<T: Num + Copy + Neg<Output = T>> Point3<T> {
+    /// Reflects the point over the plane where x is zero.
+    #[must_use]
+    pub fn reflect_x(self) -> Self {
+        Self { x: -self.x, ..self }
+    }
+
+    /// Reflects the point over the plane where y is zero.
+    #[must_use]
+    pub fn reflect_y(self) -> Self {
+        Self { y: -self.y, ..self }
+    }
+
+    /// Reflects the point over the plane where z is zero.
+    #[must_use]
+    pub fn reflect_z(self) -> Self {
+        Self { z: -self.z, ..self }
+    }
+}
+
+impl<T: Num + Copy> From<(T, T, T)> for Point3<T> {
+    fn from(value: (T, T, T)) -> Self {
+        Self::from_tuple(value)
+    }
+}
+
+impl<T: Num + Copy> From<Point3<T>> for (T, T, T) {
+    fn from(value: Point3<T>) -> Self {
+        value.into_tuple()
+    }
+}
+
+impl<T: Num + Copy + Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DeriveDisplay)]
+#[display("Error parsing Point3: \"{}\" is not of the form x,y,z", _0)]
+pub struct Point3ParseError(pub(crate) String);
+impl Error for Point3ParseError {}
+
+/// Splits "x,y,z" into its three trimmed components. Surrounding angle
+/// brackets or parentheses are ignored.
+fn split_xyz(s: &str) -> Option<(&str, &str, &str)> {
+    let s = s.trim();
+    let s = s
+        .strip_prefix(['<', '('])
+        .and_then(|s| s.strip_suffix(['>', ')']))
+        .unwrap_or(s);
+    s.split(',').map(str::trim).collect_tuple()
+}
+
+impl<T: Num + Copy + FromStr> FromStr for Point3<T> {
+    type Err = Point3ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || Point3ParseError(s.to_string());
+        let (x, y, z) = split_xyz(s).ok_or_else(err)?;
+        Ok(Self {
+            x: x.parse().map_err(|_| err())?,
+            y: y.parse().map_err(|_| err())?,
+            z: z.parse().map_err(|_| err())?,
+        })
+    }
+}
+
+#[must_use]
+pub const fn point3<T: Num + Copy>(x: T, y: T, z: T) -> Point3<T> {
+    Point3::new(x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algebra() {
+        let mut p = Point3::new(5, 7, 9);
+        assert_eq!(p + point3(1, 2, 3), point3(6, 9, 12));
+        assert_eq!(p + 1, point3(6, 8, 10));
+        assert_eq!(p - point3(1, 2, 3), point3(4, 5, 6));
+        assert_eq!(p * 2, (10, 14, 18).into());
+        assert_eq!(-p, (-5, -7, -9).into());
+        assert_eq!(p % 4, (1, 3, 1).into());
+
+        p += (1, 1, 1).into();
+        assert_eq!(p, (6, 8, 10).into());
+    }
+
+    #[test]
+    fn apply() {
+        let p = Point3::new(1u32, 1, 1);
+        assert_eq!(p.apply(Vec3::new(1, -1, 0)), Some((2, 0, 1).into()));
+        assert_eq!(p.apply(Vec3::new(0, 0, -2)), None);
+        assert_eq!(p.apply_n(Vec3::new(0, 1, 0), 3), Some((1, 4, 1).into()));
+    }
+
+    #[test]
+    fn dist() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, 0, 3);
+        assert_eq!(a.manhattan_dist(b), 5);
+        assert_eq!(a.dist_squared(b), 13);
+        assert_eq!(Point3::new(0.0, 0.0, 0.0).dist((2.0, 3.0, 6.0).into()), 7.0);
+    }
+
+    #[test]
+    fn within() {
+        let a = (0, 0, 0).into();
+        let b = (2, 2, 2).into();
+        assert!(Point3::new(1, 1, 1).within(a, b));
+        assert!(!Point3::new(1, 2, 1).within(a, b));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("1,2,3".parse(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!("<-1, 2, 3>".parse(), Ok(Point3::new(-1, 2, 3)));
+        assert_eq!(
+            "1,2".parse::<Point3<i32>>(),
+            Err(Point3ParseError("1,2".to_string()))
+        );
+        assert!("1,a,3".parse::<Point3<i32>>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "(1, 2, 3)");
+    }
+}
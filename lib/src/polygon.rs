@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+
+use num::{CheckedAdd, CheckedSub, Integer, Num, ToPrimitive};
+
+use crate::{Offset, Point2};
+
+/// A simple polygon on the integer lattice. The last vertex is implicitly
+/// connected back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon<T: Num + Copy> {
+    vertices: Vec<Point2<T>>,
+}
+
+/// Converts a point to i64 coordinates so signed math works for usize points.
+fn signed<T: Num + Copy + ToPrimitive>(p: Point2<T>) -> (i64, i64) {
+    (
+        p.x.to_i64()
+            .expect("polygon coordinate doesn't fit in an i64"),
+        p.y.to_i64()
+            .expect("polygon coordinate doesn't fit in an i64"),
+    )
+}
+
+impl<T: Num + Copy + ToPrimitive> Polygon<T> {
+    /// Creates a polygon from its vertices in order.
+    #[must_use]
+    pub const fn new(vertices: Vec<Point2<T>>) -> Self {
+        Self { vertices }
+    }
+
+    /// Creates a polygon by walking from a start point, applying each
+    /// direction len times and placing a vertex after every move. Returns None
+    /// if a move overflows the coordinate type.
+    pub fn from_moves<D: Offset>(
+        start: impl Into<Point2<T>>,
+        moves: impl IntoIterator<Item = (D, T)>,
+    ) -> Option<Self>
+    where
+        T: CheckedAdd + CheckedSub + TryFrom<isize>,
+    {
+        let mut pos = start.into();
+        let mut vertices = vec![];
+        for (dir, len) in moves {
+            pos = pos.apply_n(dir, len)?;
+            vertices.push(pos);
+        }
+        Some(Self::new(vertices))
+    }
+
+    #[must_use]
+    pub fn vertices(&self) -> &[Point2<T>] {
+        &self.vertices
+    }
+
+    /// Iterates over every edge as a pair of vertices, including the closing
+    /// edge from the last vertex to the first.
+    pub fn edges(&self) -> impl Iterator<Item = (Point2<T>, Point2<T>)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    fn signed_edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+        self.edges().map(|(a, b)| (signed(a), signed(b)))
+    }
+
+    /// Returns twice the signed area using the shoelace formula. The result is
+    /// positive when the vertices go clockwise with y pointing south, which is
+    /// the orientation used by [`crate::Dir`].
+    #[must_use]
+    pub fn twice_signed_area(&self) -> i64 {
+        self.signed_edges()
+            .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+            .sum()
+    }
+
+    /// Returns the area enclosed by the polygon.
+    #[must_use]
+    pub fn area(&self) -> f64 {
+        self.twice_signed_area().unsigned_abs() as f64 / 2.0
+    }
+
+    /// Returns the sum of the manhattan lengths of every edge.
+    #[must_use]
+    pub fn manhattan_perimeter(&self) -> u64 {
+        self.signed_edges()
+            .map(|((x1, y1), (x2, y2))| x1.abs_diff(x2) + y1.abs_diff(y2))
+            .sum()
+    }
+
+    /// Returns the number of lattice points on the boundary of the polygon.
+    /// Polygons without any area double back over their own edges, so their
+    /// points are only counted once.
+    #[must_use]
+    pub fn boundary_points(&self) -> u64 {
+        if self.twice_signed_area() == 0 {
+            let mut points = HashSet::new();
+            for ((x1, y1), (x2, y2)) in self.signed_edges() {
+                let steps = (x2 - x1).unsigned_abs().gcd(&(y2 - y1).unsigned_abs()) as i64;
+                points.insert((x1, y1));
+                for i in 1..=steps {
+                    points.insert((x1 + (x2 - x1) / steps * i, y1 + (y2 - y1) / steps * i));
+                }
+            }
+            return points.len() as u64;
+        }
+        self.signed_edges()
+            .map(|((x1, y1), (x2, y2))| (x2 - x1).unsigned_abs().gcd(&(y2 - y1).unsigned_abs()))
+            .sum()
+    }
+
+    /// Returns the number of lattice points strictly inside the polygon using
+    /// Pick's theorem. Polygons without any area, such as empty ones or ones
+    /// with every vertex on a line, have no interior points.
+    #[must_use]
+    pub fn interior_points(&self) -> u64 {
+        let twice_area = self.twice_signed_area().unsigned_abs();
+        if twice_area == 0 {
+            return 0;
+        }
+        (twice_area + 2).saturating_sub(self.boundary_points()) / 2
+    }
+
+    /// Returns the number of lattice points inside or on the boundary of the
+    /// polygon. For a polygon traced through the centers of grid cells, this is
+    /// the number of cells it covers.
+    #[must_use]
+    pub fn lattice_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Returns whether a point lies on one of the polygon's edges.
+    pub fn on_boundary(&self, p: impl Into<Point2<T>>) -> bool {
+        let (px, py) = signed(p.into());
+        self.signed_edges().any(|((x1, y1), (x2, y2))| {
+            let cross = (x2 - x1) * (py - y1) - (y2 - y1) * (px - x1);
+            cross == 0
+                && px >= x1.min(x2)
+                && px <= x1.max(x2)
+                && py >= y1.min(y2)
+                && py <= y1.max(y2)
+        })
+    }
+
+    /// Returns whether a point is inside the polygon or on its boundary.
+    pub fn contains(&self, p: impl Into<Point2<T>>) -> bool {
+        let p = p.into();
+        if self.on_boundary(p) {
+            return true;
+        }
+
+        // casts a ray towards positive x and counts the edges it crosses
+        let (px, py) = signed(p);
+        self.signed_edges()
+            .filter(|&((x1, y1), (x2, y2))| {
+                if (y1 > py) == (y2 > py) {
+                    return false;
+                }
+                // the crossing is right of p when (x - px) > 0, compared
+                // without dividing by (y2 - y1)
+                let lhs = (x2 - x1) * (py - y1) + (x1 - px) * (y2 - y1);
+                (lhs > 0) == (y2 > y1)
+            })
+            .count()
+            % 2
+            == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Dir;
+
+    use super::*;
+
+    const DIG_PLAN: &str = "R 6
+D 5
+L 2
+D 2
+R 2
+D 2
+L 5
+U 2
+L 1
+U 2
+R 2
+U 3
+L 2
+U 2";
+
+    fn dig_plan<T>(start: Point2<T>) -> Option<Polygon<T>>
+    where
+        T: Num + Copy + ToPrimitive + CheckedAdd + CheckedSub + TryFrom<isize> + std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+    {
+        Polygon::from_moves(
+            start,
+            DIG_PLAN.lines().map(|l| {
+                let (dir, len) = l.split_once(' ').unwrap();
+                (dir.parse::<Dir>().unwrap(), len.parse().unwrap())
+            }),
+        )
+    }
+
+    #[test]
+    fn dig_plan_area() {
+        let poly = dig_plan(Point2::new(0i64, 0)).unwrap();
+        assert_eq!(poly.twice_signed_area(), 84);
+        assert_eq!(poly.boundary_points(), 38);
+        assert_eq!(poly.manhattan_perimeter(), 38);
+        assert_eq!(poly.interior_points(), 24);
+        assert_eq!(poly.lattice_points(), 62);
+
+        // the same plan works with unsigned points as long as it never goes
+        // negative
+        let poly = dig_plan(Point2::new(0usize, 0)).unwrap();
+        assert_eq!(poly.lattice_points(), 62);
+        assert!(Polygon::from_moves(Point2::new(0usize, 0), [(Dir::West, 1)]).is_none());
+    }
+
+    #[test]
+    fn orientation() {
+        let square = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(4, 0),
+            Point2::new(4, 4),
+            Point2::new(0, 4),
+        ]);
+        assert_eq!(square.twice_signed_area(), 32);
+        assert_eq!(square.area(), 16.0);
+        let reversed = Polygon::new(square.vertices().iter().rev().copied().collect());
+        assert_eq!(reversed.twice_signed_area(), -32);
+        assert_eq!(reversed.interior_points(), 9);
+    }
+
+    #[test]
+    fn diagonal_edges() {
+        let triangle = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(4, 0),
+            Point2::new(0, 4),
+        ]);
+        assert_eq!(triangle.area(), 8.0);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 3);
+        assert!(triangle.on_boundary((2, 2)));
+        assert!(triangle.contains((1, 1)));
+        assert!(!triangle.contains((3, 2)));
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(Polygon::<i64>::new(vec![]).interior_points(), 0);
+        let segment = Polygon::new(vec![Point2::new(0, 0), Point2::new(5, 0)]);
+        assert_eq!(segment.interior_points(), 0);
+        assert_eq!(segment.boundary_points(), 6);
+        assert_eq!(segment.lattice_points(), 6);
+        assert_eq!(Polygon::new(vec![Point2::new(3, 4)]).lattice_points(), 1);
+        assert_eq!(Polygon::<i64>::new(vec![]).lattice_points(), 0);
+        let collinear = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(2, 2),
+            Point2::new(6, 6),
+        ]);
+        assert_eq!(collinear.area(), 0.0);
+        assert_eq!(collinear.interior_points(), 0);
+        assert_eq!(collinear.lattice_points(), 7);
+    }
+
+    #[test]
+    fn contains() {
+        // a U shape so that the ray crosses several edges
+        let poly = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(2, 0),
+            Point2::new(2, 3),
+            Point2::new(4, 3),
+            Point2::new(4, 0),
+            Point2::new(6, 0),
+            Point2::new(6, 5),
+            Point2::new(0, 5),
+        ]);
+        assert!(poly.contains((1, 1)));
+        assert!(poly.contains((5, 1)));
+        assert!(!poly.contains((3, 1)));
+        assert!(poly.contains((3, 4)));
+        assert!(poly.contains((3, 3)));
+        assert!(!poly.on_boundary((3, 4)));
+        assert!(!poly.contains((7, 1)));
+        assert!(!poly.contains((-1, 3)));
+        let inside = (0..=6)
+            .flat_map(|x| (0..=5).map(move |y| (x, y)))
+            .filter(|p| poly.contains(*p))
+            .count() as u64;
+        assert_eq!(inside, poly.lattice_points());
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::{self, Display};
+
+use num::{Num, ToPrimitive, iter::range};
+
+use crate::Point2;
+
+/// An axis aligned rectangle covering [min, max) on both axes, the same
+/// convention as [`Point2::within`].
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct Rect<T: Num + Copy> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+impl<T: Num + Copy + PartialOrd> Rect<T> {
+    /// Creates a rect from its inclusive lower corner and exclusive upper
+    /// corner.
+    #[must_use]
+    pub fn new(min: impl Into<Point2<T>>, max: impl Into<Point2<T>>) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+
+    /// Creates a rect from its lower corner and its size.
+    #[must_use]
+    pub fn from_size(min: impl Into<Point2<T>>, width: T, height: T) -> Self {
+        let min = min.into();
+        Self::new(min, Point2::new(min.x + width, min.y + height))
+    }
+
+    /// Creates the smallest rect containing two opposite corners, both of
+    /// which are included.
+    #[must_use]
+    pub fn from_corners(a: impl Into<Point2<T>>, b: impl Into<Point2<T>>) -> Self {
+        let (a, b) = (a.into(), b.into());
+        Self::new(
+            Point2::new(min(a.x, b.x), min(a.y, b.y)),
+            Point2::new(max(a.x, b.x), max(a.y, b.y)) + T::one(),
+        )
+    }
+
+    /// Creates the smallest rect containing every point, or None if there are
+    /// no points.
+    pub fn bounding(points: impl IntoIterator<Item = impl Into<Point2<T>>>) -> Option<Self> {
+        let mut points = points.into_iter().map(Into::into);
+        let first = points.next()?;
+        let (lower, upper) = points.fold((first, first), |(lower, upper), p| {
+            (
+                Point2::new(min(lower.x, p.x), min(lower.y, p.y)),
+                Point2::new(max(upper.x, p.x), max(upper.y, p.y)),
+            )
+        });
+        Some(Self::new(lower, upper + T::one()))
+    }
+
+    #[must_use]
+    pub fn width(&self) -> T {
+        size_between(self.min.x, self.max.x)
+    }
+
+    #[must_use]
+    pub fn height(&self) -> T {
+        size_between(self.min.y, self.max.y)
+    }
+
+    /// Returns the width and height as a point.
+    #[must_use]
+    pub fn size(&self) -> Point2<T> {
+        Point2::new(self.width(), self.height())
+    }
+
+    #[must_use]
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    /// Returns whether the rect contains no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.width() == T::zero() || self.height() == T::zero()
+    }
+
+    /// Checks if a point is inside the rect.
+    pub fn contains(&self, p: impl Into<Point2<T>>) -> bool {
+        p.into().within(self.min, self.max)
+    }
+
+    /// Checks if another rect is entirely inside this one.
+    #[must_use]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        other.is_empty()
+            || (other.min.x >= self.min.x
+                && other.min.y >= self.min.y
+                && other.max.x <= self.max.x
+                && other.max.y <= self.max.y)
+    }
+
+    /// Returns the overlap of two rects, or None if they don't overlap.
+    #[must_use]
+    pub fn intersection(&self, other: Self) -> Option<Self> {
+        let res = Self::new(
+            Point2::new(max(self.min.x, other.min.x), max(self.min.y, other.min.y)),
+            Point2::new(min(self.max.x, other.max.x), min(self.max.y, other.max.y)),
+        );
+        (!res.is_empty()).then_some(res)
+    }
+
+    /// Returns the smallest rect containing both rects.
+    #[must_use]
+    pub fn union(&self, other: Self) -> Self {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return other;
+        }
+        Self::new(
+            Point2::new(min(self.min.x, other.min.x), min(self.min.y, other.min.y)),
+            Point2::new(max(self.max.x, other.max.x), max(self.max.y, other.max.y)),
+        )
+    }
+
+    /// Splits the rect into the parts left of x and from x onwards.
+    #[must_use]
+    pub fn split_x(&self, x: T) -> (Self, Self) {
+        let x = max(self.min.x, min(x, self.max.x));
+        (
+            Self::new(self.min, Point2::new(x, self.max.y)),
+            Self::new(Point2::new(x, self.min.y), self.max),
+        )
+    }
+
+    /// Splits the rect into the parts above y and from y onwards.
+    #[must_use]
+    pub fn split_y(&self, y: T) -> (Self, Self) {
+        let y = max(self.min.y, min(y, self.max.y));
+        (
+            Self::new(self.min, Point2::new(self.max.x, y)),
+            Self::new(Point2::new(self.min.x, y), self.max),
+        )
+    }
+
+    /// Splits the rect into a left and right half. If the width is odd, the
+    /// middle column belongs to neither half.
+    #[must_use]
+    pub fn halves_x(&self) -> (Self, Self) {
+        let two = T::one() + T::one();
+        let (left, _) = self.split_x(self.min.x + self.width() / two);
+        let (_, right) = self.split_x(self.max.x - self.width() / two);
+        (left, right)
+    }
+
+    /// Splits the rect into a top and bottom half. If the height is odd, the
+    /// middle row belongs to neither half.
+    #[must_use]
+    pub fn halves_y(&self) -> (Self, Self) {
+        let two = T::one() + T::one();
+        let (top, _) = self.split_y(self.min.y + self.height() / two);
+        let (_, bottom) = self.split_y(self.max.y - self.height() / two);
+        (top, bottom)
+    }
+
+    /// Splits the rect into quadrants in the order top left, top right, bottom
+    /// left, bottom right. A middle row or column is left out like in
+    /// [`Rect::halves_x`].
+    #[must_use]
+    pub fn quadrants(&self) -> [Self; 4] {
+        let (top, bottom) = self.halves_y();
+        let (top_left, top_right) = top.halves_x();
+        let (bottom_left, bottom_right) = bottom.halves_x();
+        [top_left, top_right, bottom_left, bottom_right]
+    }
+
+    /// Iterates over every point in the rect, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2<T>> + Clone + use<T>
+    where
+        T: ToPrimitive,
+    {
+        let Self { min, max } = *self;
+        range(min.y, max.y).flat_map(move |y| range(min.x, max.x).map(move |x| Point2::new(x, y)))
+    }
+}
+
+/// Returns b - a, or zero if the range is inverted, so unsigned rects never
+/// underflow.
+fn size_between<T: Num + Copy + PartialOrd>(a: T, b: T) -> T {
+    if b > a { b - a } else { T::zero() }
+}
+
+impl<T: Num + Copy + Display> Display for Rect<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to {}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::{Itertools, assert_equal};
+
+    use crate::{Dir, Entity, Grid};
+
+    use super::*;
+
+    #[test]
+    fn constructors() {
+        let rect = Rect::from_corners((5, 1), (2, 3));
+        assert_eq!(rect, Rect::new((2, 1), (6, 4)));
+        assert_eq!(rect, Rect::from_size((2, 1), 4, 3));
+        assert_eq!(rect.size(), Point2::new(4, 3));
+        assert_eq!(rect.area(), 12);
+        assert_eq!(
+            Rect::bounding([(3, 3), (1, 7), (4, 2)]),
+            Some(Rect::new((1, 2), (5, 8)))
+        );
+        assert_eq!(Rect::<i32>::bounding(Vec::<(i32, i32)>::new()), None);
+        assert!(Rect::new((3usize, 0), (1, 5)).is_empty());
+        assert_eq!(Rect::new((3usize, 0), (1, 5)).area(), 0);
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::new((0, 0), (3, 3));
+        assert!(rect.contains((0, 0)));
+        assert!(rect.contains((2, 2)));
+        assert!(!rect.contains((3, 2)));
+        assert!(!rect.contains((-1, 0)));
+        assert!(rect.contains_rect(Rect::new((1, 1), (3, 2))));
+        assert!(!rect.contains_rect(Rect::new((1, 1), (4, 2))));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = Rect::new((0, 0), (4, 4));
+        let b = Rect::new((2, 3), (6, 5));
+        assert_eq!(a.intersection(b), Some(Rect::new((2, 3), (4, 4))));
+        assert_eq!(a.union(b), Rect::new((0, 0), (6, 5)));
+        assert_eq!(a.intersection(Rect::new((4, 0), (5, 5))), None);
+        assert_eq!(a.union(Rect::default()), a);
+    }
+
+    #[test]
+    fn splitting() {
+        let rect = Rect::new((0, 0), (11usize, 7));
+        assert_eq!(
+            rect.split_x(4),
+            (Rect::new((0, 0), (4, 7)), Rect::new((4, 0), (11, 7)))
+        );
+        assert_eq!(
+            rect.halves_y(),
+            (Rect::new((0, 0), (11, 3)), Rect::new((0, 4), (11, 7)))
+        );
+        assert_eq!(
+            rect.quadrants(),
+            [
+                Rect::new((0, 0), (5, 3)),
+                Rect::new((6, 0), (11, 3)),
+                Rect::new((0, 4), (5, 7)),
+                Rect::new((6, 4), (11, 7)),
+            ]
+        );
+        let even = Rect::new((0, 0), (4, 2));
+        assert_eq!(even.quadrants().map(|q| q.area()), [2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn points() {
+        let rect = Rect::new((1, 1), (3, 3));
+        assert_equal(
+            rect.points(),
+            [(1, 1), (2, 1), (1, 2), (2, 2)].map(Point2::from_tuple),
+        );
+        assert_eq!(Rect::new((2, 0), (0, 2)).points().count(), 0);
+        assert_eq!(
+            Rect::from_size((0, 0), 5, 4).points().collect_vec().len(),
+            20
+        );
+    }
+
+    #[test]
+    fn grid() {
+        let grid = Grid::from_chars("abc\ndef\nghi").unwrap();
+        assert_eq!(grid.rect(), Rect::new((0, 0), (3, 3)));
+        let sub = grid.subgrid_rect(Rect::new((1, 1), (3, 3))).unwrap();
+        assert_eq!(sub, Grid::from_chars("ef\nhi").unwrap());
+        assert_eq!(grid.subgrid_rect(Rect::new((1, 1), (4, 3))), None);
+        assert_equal(
+            grid.view(Rect::new((1, 0), (5, 2))).map(|(_, c)| *c),
+            ['b', 'c', 'e', 'f'],
+        );
+
+        let en = Entity::new_in_rect((1, 1), Dir::East, Rect::new((0, 0), (2, 2))).unwrap();
+        assert!(en.step_bounded().is_none());
+        assert!(Entity::new_in_rect((2, 1), Dir::East, Rect::new((0, 0), (2, 2))).is_none());
+    }
+}
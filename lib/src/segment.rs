@@ -0,0 +1,418 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use num::{Integer, Num, NumCast, ToPrimitive};
+
+use crate::{Grid, Point2, Vec2};
+
+/// A line segment between two points on the integer lattice. Both endpoints
+/// are part of the segment.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct Segment<T: Num + Copy> {
+    pub a: Point2<T>,
+    pub b: Point2<T>,
+}
+
+fn to_i64<T: ToPrimitive + Copy>(v: T) -> i64 {
+    v.to_i64()
+        .expect("segment coordinate doesn't fit in an i64")
+}
+
+fn from_i64<T: NumCast>(v: i64) -> T {
+    T::from(v).expect("segment point doesn't fit in the coordinate type")
+}
+
+/// The cross product of b - a and c - a, which is positive when c is
+/// clockwise of the line from a to b with y pointing south.
+fn orient(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> i128 {
+    let wide = |v: i64| -> i128 { v.into() };
+    wide(b.0 - a.0) * wide(c.1 - a.1) - wide(b.1 - a.1) * wide(c.0 - a.0)
+}
+
+impl<T: Num + Copy + ToPrimitive + NumCast> Segment<T> {
+    /// Creates a segment from its two endpoints.
+    #[must_use]
+    pub fn new(a: impl Into<Point2<T>>, b: impl Into<Point2<T>>) -> Self {
+        Self {
+            a: a.into(),
+            b: b.into(),
+        }
+    }
+
+    fn ends(&self) -> ((i64, i64), (i64, i64)) {
+        (
+            (to_i64(self.a.x), to_i64(self.a.y)),
+            (to_i64(self.b.x), to_i64(self.b.y)),
+        )
+    }
+
+    fn delta(&self) -> (i64, i64) {
+        let ((x1, y1), (x2, y2)) = self.ends();
+        (x2 - x1, y2 - y1)
+    }
+
+    fn point(x: i64, y: i64) -> Point2<T> {
+        Point2::new(from_i64(x), from_i64(y))
+    }
+
+    #[must_use]
+    pub fn is_horizontal(&self) -> bool {
+        self.delta().1 == 0
+    }
+
+    #[must_use]
+    pub fn is_vertical(&self) -> bool {
+        self.delta().0 == 0
+    }
+
+    /// Returns whether the segment is at exactly 45 degrees.
+    #[must_use]
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = self.delta();
+        dx != 0 && dx.abs() == dy.abs()
+    }
+
+    /// Returns whether the segment is horizontal or vertical.
+    #[must_use]
+    pub fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    /// Returns the smallest step that moves along the segment from a to b
+    /// between lattice points, or None if both endpoints are the same.
+    #[must_use]
+    pub fn step(&self) -> Option<Vec2> {
+        let (dx, dy) = self.delta();
+        let g = dx.gcd(&dy);
+        (g != 0).then(|| Vec2::new((dx / g) as isize, (dy / g) as isize))
+    }
+
+    /// Returns the euclidean length of the segment.
+    #[must_use]
+    pub fn length(&self) -> f64 {
+        let (dx, dy) = self.delta();
+        (dx as f64).hypot(dy as f64)
+    }
+
+    /// Returns the manhattan distance between the endpoints.
+    #[must_use]
+    pub fn manhattan_length(&self) -> u64 {
+        let (dx, dy) = self.delta();
+        dx.unsigned_abs() + dy.unsigned_abs()
+    }
+
+    /// Returns the number of king moves between the endpoints, which is one
+    /// less than the number of cells [`Segment::cells`] yields.
+    #[must_use]
+    pub fn chebyshev_length(&self) -> u64 {
+        let (dx, dy) = self.delta();
+        dx.unsigned_abs().max(dy.unsigned_abs())
+    }
+
+    /// Iterates over every lattice point exactly on the segment, from a to b.
+    /// For horizontal, vertical and diagonal segments this is every cell the
+    /// segment passes through.
+    pub fn lattice_points(&self) -> impl Iterator<Item = Point2<T>> + Clone + use<T> {
+        let ((x, y), _) = self.ends();
+        let (dx, dy) = self.delta();
+        let n = dx.gcd(&dy);
+        let (sx, sy) = if n == 0 { (0, 0) } else { (dx / n, dy / n) };
+        (0..=n).map(move |i| Self::point(x + sx * i, y + sy * i))
+    }
+
+    /// Iterates over the cells covering the segment using Bresenham's line
+    /// algorithm, from a to b. Consecutive cells are always adjacent,
+    /// including diagonally.
+    pub fn cells(&self) -> impl Iterator<Item = Point2<T>> + Clone + use<T> {
+        let ((x, y), (end_x, end_y)) = self.ends();
+        let (dx, dy) = (self.delta().0.abs(), -self.delta().1.abs());
+        let (sx, sy) = (self.delta().0.signum(), self.delta().1.signum());
+        let mut state = Some((x, y, dx + dy));
+        std::iter::from_fn(move || {
+            let (x, y, err) = state?;
+            state = (x != end_x || y != end_y).then(|| {
+                let (mut x, mut y, mut next_err) = (x, y, err);
+                if 2 * err >= dy {
+                    next_err += dy;
+                    x += sx;
+                }
+                if 2 * err <= dx {
+                    next_err += dx;
+                    y += sy;
+                }
+                (x, y, next_err)
+            });
+            Some(Self::point(x, y))
+        })
+    }
+
+    /// Returns whether a point lies exactly on the segment.
+    pub fn contains(&self, p: impl Into<Point2<T>>) -> bool {
+        let p = p.into();
+        let p = (to_i64(p.x), to_i64(p.y));
+        let (a, b) = self.ends();
+        orient(a, b, p) == 0
+            && p.0 >= a.0.min(b.0)
+            && p.0 <= a.0.max(b.0)
+            && p.1 >= a.1.min(b.1)
+            && p.1 <= a.1.max(b.1)
+    }
+
+    /// Returns whether two segments share at least one point, including
+    /// touching endpoints and collinear overlaps.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (a, b) = self.ends();
+        let (c, d) = other.ends();
+        let (o1, o2) = (orient(a, b, c), orient(a, b, d));
+        let (o3, o4) = (orient(c, d, a), orient(c, d, b));
+        if o1.signum() * o2.signum() < 0 && o3.signum() * o4.signum() < 0 {
+            return true;
+        }
+        self.contains(other.a)
+            || self.contains(other.b)
+            || other.contains(self.a)
+            || other.contains(self.b)
+    }
+
+    /// Returns the numerators and denominator of the positions along both
+    /// segments where their lines cross, or None if they are parallel or the
+    /// crossing is outside either segment.
+    fn crossing(&self, other: &Self) -> Option<(i128, i128)> {
+        let (a, _) = self.ends();
+        let (c, _) = other.ends();
+        let (d1, d2) = (self.delta(), other.delta());
+        let denom = orient((0, 0), d1, d2);
+        if denom == 0 {
+            return None;
+        }
+        let ac = (c.0 - a.0, c.1 - a.1);
+        let (t, u) = (orient((0, 0), ac, d2), orient((0, 0), ac, d1));
+        // normalize so the denominator is positive, then check t and u are
+        // both in [0, 1]
+        let (t, u, denom) = if denom < 0 {
+            (-t, -u, -denom)
+        } else {
+            (t, u, denom)
+        };
+        ((0..=denom).contains(&t) && (0..=denom).contains(&u)).then_some((t, denom))
+    }
+
+    /// Returns the single point where two non parallel segments cross, or None
+    /// if they are parallel or don't touch. Use [`Segment::overlap`] for
+    /// collinear segments.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Point2<f64>> {
+        let ((x, y), _) = self.ends();
+        let (dx, dy) = self.delta();
+        let (t, denom) = self.crossing(other)?;
+        let t = t as f64 / denom as f64;
+        Some(Point2::new(
+            x as f64 + t * dx as f64,
+            y as f64 + t * dy as f64,
+        ))
+    }
+
+    /// Returns the crossing point of two non parallel segments if it is a
+    /// lattice point.
+    #[must_use]
+    pub fn lattice_intersection(&self, other: &Self) -> Option<Point2<T>> {
+        let ((x, y), _) = self.ends();
+        let (dx, dy) = self.delta();
+        let (t, denom) = self.crossing(other)?;
+        let (dx, dy): (i128, i128) = (dx.into(), dy.into());
+        let (nx, ny) = (t * dx, t * dy);
+        (nx % denom == 0 && ny % denom == 0)
+            .then(|| Self::point(x + (nx / denom) as i64, y + (ny / denom) as i64))
+    }
+
+    /// Returns the part shared by two collinear segments, or None if they
+    /// aren't collinear or don't overlap. The result goes in the same
+    /// direction as self.
+    #[must_use]
+    pub fn overlap(&self, other: &Self) -> Option<Self> {
+        let (a, b) = self.ends();
+        let (c, d) = other.ends();
+        if a == b {
+            return other.contains(self.a).then_some(*self);
+        }
+        if orient(a, b, c) != 0 || orient(a, b, d) != 0 {
+            return None;
+        }
+        // collinear points are ordered along the line by their coordinates
+        let forward = a <= b;
+        let (lo, hi) = (a.min(b).max(c.min(d)), a.max(b).min(c.max(d)));
+        (lo <= hi).then(|| {
+            let (lo, hi) = (Self::point(lo.0, lo.1), Self::point(hi.0, hi.1));
+            if forward {
+                Self::new(lo, hi)
+            } else {
+                Self::new(hi, lo)
+            }
+        })
+    }
+
+    /// Counts how many segments cover each cell, storing only the cells that
+    /// are covered at least once.
+    pub fn rasterize_counts<'a>(
+        segments: impl IntoIterator<Item = &'a Self>,
+    ) -> HashMap<Point2<T>, usize>
+    where
+        T: Hash + Eq + 'a,
+    {
+        let mut counts = HashMap::new();
+        for p in segments.into_iter().flat_map(Self::cells) {
+            *counts.entry(p).or_default() += 1;
+        }
+        counts
+    }
+}
+
+impl Segment<usize> {
+    /// Sets every cell the segment covers on a grid. Cells outside of the grid
+    /// are ignored.
+    pub fn draw<C: Clone>(&self, grid: &mut Grid<C>, cell: &C) {
+        grid.apply(self.cells(), cell);
+    }
+}
+
+impl<T: Num + Copy + Display> Display for Segment<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.a, self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::{Itertools, assert_equal};
+
+    use crate::point2;
+
+    use super::*;
+
+    // the example vents from 2021 day 5
+    const VENTS: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    fn vents() -> Vec<Segment<usize>> {
+        VENTS
+            .lines()
+            .map(|l| {
+                let (a, b) = l.split_once(" -> ").unwrap();
+                let parse = |s: &str| {
+                    let (x, y) = s.split_once(',').unwrap();
+                    point2(x.parse().unwrap(), y.parse().unwrap())
+                };
+                Segment::new(parse(a), parse(b))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn vent_overlaps() {
+        let vents = vents();
+        let straight = vents.iter().filter(|s| s.is_axis_aligned());
+        let counts = Segment::rasterize_counts(straight);
+        assert_eq!(counts.values().filter(|c| **c >= 2).count(), 5);
+        let counts = Segment::rasterize_counts(&vents);
+        assert_eq!(counts.values().filter(|c| **c >= 2).count(), 12);
+        assert!(vents.iter().all(|s| s.is_axis_aligned() || s.is_diagonal()));
+    }
+
+    #[test]
+    fn lattice_points() {
+        let seg = Segment::new((0, 0), (6, 4));
+        assert_equal(
+            seg.lattice_points(),
+            [(0, 0), (3, 2), (6, 4)].map(Point2::from_tuple),
+        );
+        assert_eq!(seg.step(), Some(Vec2::new(3, 2)));
+        assert_eq!(Segment::new((1, 1), (1, 1)).step(), None);
+        assert_eq!(Segment::new((1, 1), (1, 1)).lattice_points().count(), 1);
+        assert_equal(
+            Segment::new((3usize, 1), (1, 3)).lattice_points(),
+            [(3, 1), (2, 2), (1, 3)].map(Point2::from_tuple),
+        );
+    }
+
+    #[test]
+    fn bresenham() {
+        let seg = Segment::new((0, 0), (5, 2));
+        let cells = seg.cells().collect_vec();
+        assert_eq!(
+            cells,
+            [(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)].map(Point2::from_tuple)
+        );
+        assert_eq!(cells.len() as u64, seg.chebyshev_length() + 1);
+
+        let reversed = Segment::new((5i64, -2), (0, 0)).cells().collect_vec();
+        assert_eq!(reversed.len(), 6);
+        assert_eq!(reversed.first(), Some(&Point2::new(5, -2)));
+        assert_eq!(reversed.last(), Some(&Point2::new(0, 0)));
+        assert!(
+            reversed
+                .iter()
+                .tuple_windows()
+                .all(|(a, b)| (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1)
+        );
+    }
+
+    #[test]
+    fn lengths() {
+        let seg = Segment::new((1, 1), (4, 5));
+        assert_eq!(seg.length(), 5.0);
+        assert_eq!(seg.manhattan_length(), 7);
+        assert_eq!(seg.chebyshev_length(), 4);
+    }
+
+    #[test]
+    fn intersections() {
+        let a = Segment::new((0, 0), (4, 4));
+        let b = Segment::new((0, 4), (4, 0));
+        assert!(a.intersects(&b));
+        assert_eq!(a.intersection(&b), Some(Point2::new(2.0, 2.0)));
+        assert_eq!(a.lattice_intersection(&b), Some(Point2::new(2, 2)));
+
+        let c = Segment::new((0, 1), (1, 0));
+        assert_eq!(a.intersection(&c), Some(Point2::new(0.5, 0.5)));
+        assert_eq!(a.lattice_intersection(&c), None);
+
+        let d = Segment::new((2, 0), (3, 0));
+        assert!(!b.intersects(&d));
+        assert_eq!(b.intersection(&d), None);
+        assert!(Segment::new((4, 0), (5, 0)).intersects(&b));
+
+        // parallel segments never have a single crossing point
+        assert_eq!(a.intersection(&Segment::new((1, 1), (6, 6))), None);
+    }
+
+    #[test]
+    fn overlap() {
+        let a = Segment::new((0, 0), (6, 3));
+        let b = Segment::new((8, 4), (2, 1));
+        assert_eq!(a.overlap(&b), Some(Segment::new((2, 1), (6, 3))));
+        assert_eq!(b.overlap(&a), Some(Segment::new((6, 3), (2, 1))));
+        assert!(a.intersects(&b));
+        assert_eq!(a.overlap(&Segment::new((8, 4), (10, 5))), None);
+        assert_eq!(a.overlap(&Segment::new((0, 1), (6, 4))), None);
+        assert_eq!(
+            Segment::new((4, 2), (4, 2)).overlap(&a),
+            Some(Segment::new((4, 2), (4, 2)))
+        );
+    }
+
+    #[test]
+    fn draw() {
+        let mut grid = Grid::new_filled('.', 4, 3);
+        Segment::new((0, 0), (3, 2)).draw(&mut grid, &'#');
+        Segment::new((0, 2), (9, 2)).draw(&mut grid, &'-');
+        assert_eq!(grid, Grid::from_chars("#...\n.##.\n----").unwrap());
+    }
+}
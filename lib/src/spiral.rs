@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use num::integer::Roots;
+
+use crate::{Point2, Vec2};
+
+// The spiral starts at the origin, steps east, then turns left (north, towards
+// negative y) and keeps turning left, so square 1 of an Ulam spiral is index 0.
+//
+//  4  3  2
+//  5  0  1
+//  6  7  8  9
+
+/// Returns the position of the nth square of the spiral, where index 0 is the
+/// origin.
+#[must_use]
+pub fn spiral_index_to_point(n: u64) -> Point2<i64> {
+    if n == 0 {
+        return Point2::new(0, 0);
+    }
+    // ring k holds the indices from (2k - 1)^2 up to (2k + 1)^2 - 1
+    let k = n.sqrt().div_ceil(2) as i64;
+    let m = (n - (2 * k as u64 - 1).pow(2)) as i64;
+    let (side, off) = (m / (2 * k), m % (2 * k));
+    match side {
+        0 => Point2::new(k, k - 1 - off),
+        1 => Point2::new(k - 1 - off, -k),
+        2 => Point2::new(-k, -k + 1 + off),
+        _ => Point2::new(-k + 1 + off, k),
+    }
+}
+
+/// Returns the index of a position in the spiral. This is the inverse of
+/// [`spiral_index_to_point`].
+#[must_use]
+pub fn spiral_point_to_index(p: impl Into<Point2<i64>>) -> u64 {
+    let Point2 { x, y } = p.into();
+    let k = x.abs().max(y.abs());
+    if k == 0 {
+        return 0;
+    }
+    let base = (2 * k - 1).pow(2);
+    let i = if x == k && y < k {
+        base + k - 1 - y
+    } else if y == -k && x < k {
+        base + 2 * k + k - 1 - x
+    } else if x == -k && y > -k {
+        base + 4 * k + y + k - 1
+    } else {
+        base + 6 * k + x + k - 1
+    };
+    i as u64
+}
+
+/// Iterates over the positions of the spiral in order, starting at the
+/// origin.
+pub fn spiral_points() -> impl Iterator<Item = Point2<i64>> + Clone {
+    (0..).map(spiral_index_to_point)
+}
+
+/// Fills the spiral in order, yielding each value as it is placed. The first
+/// square gets first, and every later square gets the result of f called with
+/// its position and the values of its already filled neighbours, including
+/// diagonals.
+pub fn spiral_accumulate<V: Clone>(
+    first: V,
+    mut f: impl FnMut(Point2<i64>, &[V]) -> V,
+) -> impl Iterator<Item = V> {
+    let mut filled = HashMap::new();
+    let mut points = spiral_points();
+    std::iter::from_fn(move || {
+        let p = points.next()?;
+        let value = if filled.is_empty() {
+            first.clone()
+        } else {
+            let neighbours = Vec2::SURROUNDING
+                .into_iter()
+                .filter_map(|o| p.apply(o).and_then(|n| filled.get(&n)))
+                .cloned()
+                .collect::<Vec<_>>();
+            f(p, &neighbours)
+        };
+        filled.insert(p, value.clone());
+        Some(value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::{Itertools, assert_equal};
+
+    use super::*;
+
+    #[test]
+    fn first_ring() {
+        assert_equal(
+            spiral_points().take(10),
+            [
+                (0, 0),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (2, 1),
+            ]
+            .map(Point2::from_tuple),
+        );
+    }
+
+    #[test]
+    fn consecutive_squares_touch() {
+        assert!(
+            spiral_points()
+                .take(1000)
+                .tuple_windows()
+                .all(|(a, b)| a.manhattan_dist(b) == 1)
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in 0..10_000 {
+            assert_eq!(spiral_point_to_index(spiral_index_to_point(n)), n);
+        }
+        for x in -20..=20 {
+            for y in -20..=20 {
+                assert_eq!(
+                    spiral_index_to_point(spiral_point_to_index((x, y))),
+                    Point2::new(x, y)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        let dist =
+            |square: u64| spiral_index_to_point(square - 1).manhattan_dist(Point2::new(0, 0));
+        assert_eq!(dist(1), 0);
+        assert_eq!(dist(12), 3);
+        assert_eq!(dist(23), 2);
+        assert_eq!(dist(1024), 31);
+    }
+
+    #[test]
+    fn accumulate() {
+        assert_equal(
+            spiral_accumulate(1, |_, n| n.iter().sum::<u32>()).take(23),
+            [
+                1, 1, 2, 4, 5, 10, 11, 23, 25, 26, 54, 57, 59, 122, 133, 142, 147, 304, 330, 351,
+                362, 747, 806,
+            ],
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use num::{Num, NumCast, ToPrimitive};
+
+use crate::{Dir, Point2, Vec2};
+
+/// An integer affine transform of the plane: a 2x2 matrix followed by a
+/// translation. Rotations follow [`Dir`], so with y pointing south a right
+/// turn is clockwise on screen.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Transform {
+    m: [[i64; 2]; 2],
+    t: (i64, i64),
+}
+
+/// Returns the cell at row i and column j of the matrix product lhs * rhs.
+const fn mul_cell(lhs: [[i64; 2]; 2], rhs: [[i64; 2]; 2], i: usize, j: usize) -> i64 {
+    lhs[i][0] * rhs[0][j] + lhs[i][1] * rhs[1][j]
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// Leaves every point where it is.
+    pub const IDENTITY: Transform = Transform::new([[1, 0], [0, 1]], (0, 0));
+
+    /// Rotates by 90 degrees clockwise about the origin, the same as
+    /// [`crate::Offset::turn_right`].
+    pub const ROTATE_RIGHT: Transform = Transform::new([[0, -1], [1, 0]], (0, 0));
+
+    /// Rotates by 90 degrees counterclockwise about the origin, the same as
+    /// [`crate::Offset::turn_left`].
+    pub const ROTATE_LEFT: Transform = Transform::new([[0, 1], [-1, 0]], (0, 0));
+
+    /// Rotates by 180 degrees about the origin.
+    pub const ROTATE_180: Transform = Transform::new([[-1, 0], [0, -1]], (0, 0));
+
+    /// Flips y, the same as [`Point2::reflect_x`].
+    pub const REFLECT_X: Transform = Transform::new([[1, 0], [0, -1]], (0, 0));
+
+    /// Flips x, the same as [`Point2::reflect_y`].
+    pub const REFLECT_Y: Transform = Transform::new([[-1, 0], [0, 1]], (0, 0));
+
+    /// Swaps x and y, the same as [`Point2::swap`].
+    pub const TRANSPOSE: Transform = Transform::new([[0, 1], [1, 0]], (0, 0));
+
+    /// The 8 rotations and reflections of a square, starting with the
+    /// identity. Useful for trying every orientation of a shape.
+    pub const ORIENTATIONS: [Transform; 8] = [
+        Transform::IDENTITY,
+        Transform::ROTATE_RIGHT,
+        Transform::ROTATE_180,
+        Transform::ROTATE_LEFT,
+        Transform::REFLECT_Y,
+        Transform::TRANSPOSE,
+        Transform::REFLECT_X,
+        Transform::new([[0, -1], [-1, 0]], (0, 0)),
+    ];
+
+    /// Creates a transform from a row major matrix and a translation applied
+    /// after it.
+    #[must_use]
+    pub const fn new(m: [[i64; 2]; 2], t: (i64, i64)) -> Self {
+        Self { m, t }
+    }
+
+    /// Creates a transform that moves every point by an offset.
+    #[must_use]
+    pub fn translate(offset: impl Into<Vec2>) -> Self {
+        let v: Vec2 = offset.into();
+        Self::new(Self::IDENTITY.m, (v.x as i64, v.y as i64))
+    }
+
+    /// Returns the matrix part of the transform.
+    #[must_use]
+    pub const fn matrix(self) -> [[i64; 2]; 2] {
+        self.m
+    }
+
+    /// Returns the translation part of the transform.
+    #[must_use]
+    pub const fn translation(self) -> (i64, i64) {
+        self.t
+    }
+
+    const fn map(self, (x, y): (i64, i64)) -> (i64, i64) {
+        (
+            self.m[0][0] * x + self.m[0][1] * y + self.t.0,
+            self.m[1][0] * x + self.m[1][1] * y + self.t.1,
+        )
+    }
+
+    /// Returns the transform that applies self and then other.
+    #[must_use]
+    pub const fn then(self, other: Self) -> Self {
+        let (lhs, rhs) = (other.m, self.m);
+        Self::new(
+            [
+                [mul_cell(lhs, rhs, 0, 0), mul_cell(lhs, rhs, 0, 1)],
+                [mul_cell(lhs, rhs, 1, 0), mul_cell(lhs, rhs, 1, 1)],
+            ],
+            other.map(self.t),
+        )
+    }
+
+    /// Returns the transform applied n times.
+    #[must_use]
+    pub const fn pow(self, n: u32) -> Self {
+        let mut res = Self::IDENTITY;
+        let mut i = 0;
+        while i < n {
+            res = res.then(self);
+            i += 1;
+        }
+        res
+    }
+
+    /// Makes the transform act around a pivot instead of the origin, so the
+    /// pivot stays in place.
+    #[must_use]
+    pub fn about(self, pivot: Point2<i64>) -> Self {
+        Self::translate(Vec2::new(-pivot.x as isize, -pivot.y as isize))
+            .then(self)
+            .then(Self::translate(Vec2::new(
+                pivot.x as isize,
+                pivot.y as isize,
+            )))
+    }
+
+    /// Returns the determinant of the matrix. Rotations have a determinant of
+    /// 1 and reflections -1.
+    #[must_use]
+    pub const fn det(self) -> i64 {
+        self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
+    }
+
+    /// Returns the transform that undoes this one, or None if it has no
+    /// integer inverse.
+    #[must_use]
+    pub const fn inverse(self) -> Option<Self> {
+        let det = self.det();
+        if det != 1 && det != -1 {
+            return None;
+        }
+        let [[a, b], [c, d]] = self.m;
+        let inv = Self::new([[d * det, -b * det], [-c * det, a * det]], (0, 0));
+        let (tx, ty) = inv.map(self.t);
+        Some(Self::new(inv.m, (-tx, -ty)))
+    }
+
+    /// Transforms a point. Returns None if the result doesn't fit in the
+    /// coordinate type, such as a negative usize.
+    #[must_use]
+    pub fn apply<T: Num + Copy + ToPrimitive + NumCast>(self, p: Point2<T>) -> Option<Point2<T>> {
+        let (x, y) = self.map((p.x.to_i64()?, p.y.to_i64()?));
+        Some(Point2::new(T::from(x)?, T::from(y)?))
+    }
+
+    /// Transforms a vector, ignoring the translation.
+    #[must_use]
+    pub const fn apply_vec(self, v: Vec2) -> Vec2 {
+        let linear = Self::new(self.m, (0, 0));
+        let (x, y) = linear.map((v.x as i64, v.y as i64));
+        Vec2::new(x as isize, y as isize)
+    }
+
+    /// Transforms a direction, ignoring the translation. Returns None if the
+    /// result isn't one of the four directions.
+    #[must_use]
+    pub fn apply_dir(self, dir: Dir) -> Option<Dir> {
+        Dir::try_from(self.apply_vec(dir.into())).ok()
+    }
+
+    /// Transforms every point in a set. Returns None if any point doesn't fit
+    /// in the coordinate type.
+    pub fn apply_all<T: Num + Copy + ToPrimitive + NumCast>(
+        self,
+        points: impl IntoIterator<Item = Point2<T>>,
+    ) -> Option<Vec<Point2<T>>> {
+        points.into_iter().map(|p| self.apply(p)).collect()
+    }
+
+    /// Transforms every point in a shape, then moves the result so its
+    /// smallest x and y are both zero. Two orientations of a shape are the
+    /// same if their normalized points are equal as sets.
+    pub fn apply_normalized<T: Num + Copy + ToPrimitive + NumCast>(
+        self,
+        points: impl IntoIterator<Item = Point2<T>>,
+    ) -> Option<Vec<Point2<T>>> {
+        let moved = points
+            .into_iter()
+            .map(|p| Some(self.map((p.x.to_i64()?, p.y.to_i64()?))))
+            .collect::<Option<Vec<_>>>()?;
+        let min_x = moved.iter().map(|p| p.0).min().unwrap_or(0);
+        let min_y = moved.iter().map(|p| p.1).min().unwrap_or(0);
+        moved
+            .into_iter()
+            .map(|(x, y)| Some(Point2::new(T::from(x - min_x)?, T::from(y - min_y)?)))
+            .collect()
+    }
+}
+
+impl From<Dir> for Transform {
+    /// The rotation that turns north into the given direction.
+    fn from(value: Dir) -> Self {
+        match value {
+            Dir::North => Self::IDENTITY,
+            Dir::East => Self::ROTATE_RIGHT,
+            Dir::South => Self::ROTATE_180,
+            Dir::West => Self::ROTATE_LEFT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use itertools::Itertools;
+
+    use crate::Offset;
+
+    use super::*;
+
+    #[test]
+    fn matches_point_ops() {
+        let p = Point2::new(3i64, -5);
+        assert_eq!(Transform::REFLECT_X.apply(p), Some(p.reflect_x()));
+        assert_eq!(Transform::REFLECT_Y.apply(p), Some(p.reflect_y()));
+        assert_eq!(Transform::TRANSPOSE.apply(p), Some(p.swap()));
+        let v = Vec2::new(3, -5);
+        assert_eq!(Transform::ROTATE_RIGHT.apply_vec(v), v.turn_right());
+        assert_eq!(Transform::ROTATE_LEFT.apply_vec(v), v.turn_left());
+        assert_eq!(Transform::ROTATE_180.apply_vec(v), v.reverse());
+        for d in Dir::ORTHO {
+            assert_eq!(Transform::ROTATE_RIGHT.apply_dir(d), Some(d.turn_right()));
+            assert_eq!(Transform::from(d).apply_dir(Dir::North), Some(d));
+        }
+        assert_eq!(
+            Transform::new([[1, 1], [0, 1]], (0, 0)).apply_dir(Dir::South),
+            None
+        );
+    }
+
+    #[test]
+    fn compose() {
+        let r = Transform::ROTATE_RIGHT;
+        assert_eq!(r.then(r), Transform::ROTATE_180);
+        assert_eq!(r.pow(3), Transform::ROTATE_LEFT);
+        assert_eq!(r.pow(4), Transform::IDENTITY);
+        assert_eq!(
+            Transform::REFLECT_X.then(Transform::REFLECT_Y),
+            Transform::ROTATE_180
+        );
+
+        let t = Transform::translate(Dir::East).then(r);
+        assert_eq!(t.apply(Point2::new(0i64, 0)), Some(Point2::new(0, 1)));
+        let p = Point2::new(4i64, 7);
+        assert_eq!(t.apply(p).and_then(|p| t.inverse()?.apply(p)), Some(p));
+        assert_eq!(Transform::new([[2, 0], [0, 1]], (0, 0)).inverse(), None);
+    }
+
+    #[test]
+    fn waypoint() {
+        // rotating a waypoint about the ship instead of the origin
+        let ship = Point2::new(10, 4);
+        let waypoint = Point2::new(20, 0);
+        let t = Transform::ROTATE_RIGHT.about(ship);
+        assert_eq!(t.apply(ship), Some(ship));
+        assert_eq!(t.apply(waypoint), Some(Point2::new(14, 14)));
+        assert_eq!(
+            Transform::ROTATE_LEFT
+                .about(ship)
+                .apply(Point2::new(14, 14)),
+            Some(waypoint)
+        );
+    }
+
+    #[test]
+    fn orientations() {
+        assert!(Transform::ORIENTATIONS.iter().all_unique());
+        assert!(
+            Transform::ORIENTATIONS
+                .iter()
+                .all(|t| t.det().abs() == 1 && t.inverse().is_some())
+        );
+
+        // an L tromino has 4 distinct orientations, an S tetromino has 4 and
+        // a square has 1
+        let distinct = |shape: &[(usize, usize)]| {
+            Transform::ORIENTATIONS
+                .iter()
+                .map(|t| {
+                    t.apply_normalized(shape.iter().map(|p| Point2::from_tuple(*p)))
+                        .unwrap()
+                        .into_iter()
+                        .collect::<std::collections::BTreeSet<_>>()
+                })
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        assert_eq!(distinct(&[(0, 0), (0, 1), (1, 1)]), 4);
+        assert_eq!(distinct(&[(1, 0), (2, 0), (0, 1), (1, 1)]), 4);
+        assert_eq!(distinct(&[(0, 0), (0, 1), (1, 0), (1, 1)]), 1);
+
+        let points = [Point2::new(1usize, 0)];
+        assert_eq!(Transform::ROTATE_180.apply_all(points), None);
+        assert_eq!(
+            Transform::ROTATE_180.apply_normalized(points),
+            Some(vec![Point2::new(0, 0)])
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use num::Num;
+
+use crate::{Dir, Entity, Grid, Offset, Point2};
+
+/// Wraps an entity and records every state it moves through, so that loops
+/// can be detected as soon as a position and direction repeat.
+#[derive(Debug, Clone)]
+pub struct Walker<T: Num + Copy + Hash + Eq, D: Offset = Dir> {
+    trail: Vec<Entity<T, D>>,
+    seen: HashMap<Entity<T, D>, usize>,
+    cycle: Option<(usize, usize)>,
+}
+
+impl<T: Num + Copy + Hash + Eq, D: Offset> Walker<T, D> {
+    /// Creates a walker starting at an entity. The starting state is part of
+    /// the trail.
+    #[must_use]
+    pub fn new(start: Entity<T, D>) -> Self {
+        Self {
+            trail: vec![start],
+            seen: HashMap::from([(start, 0)]),
+            cycle: None,
+        }
+    }
+
+    /// Gets the walker's current state.
+    #[must_use]
+    pub fn entity(&self) -> Entity<T, D> {
+        *self.trail.last().unwrap()
+    }
+
+    /// Returns every state in the order it was visited, including the start.
+    #[must_use]
+    pub fn trail(&self) -> &[Entity<T, D>] {
+        &self.trail
+    }
+
+    /// Checks whether the walker has been at a position facing a direction.
+    pub fn visited(&self, pos: impl Into<Point2<T>>, dir: D) -> bool {
+        self.seen.contains_key(&Entity::new(pos, dir))
+    }
+
+    /// Moves the walker to a new state. Returns false without recording the
+    /// state if it was already visited, which means the walker is in a loop.
+    pub fn push(&mut self, next: Entity<T, D>) -> bool {
+        if let Some(&start) = self.seen.get(&next) {
+            self.cycle = Some((start, self.trail.len() - start));
+            return false;
+        }
+        self.seen.insert(next, self.trail.len());
+        self.trail.push(next);
+        true
+    }
+
+    /// Keeps moving the walker to the state returned by f until f returns
+    /// None or a state repeats. Returns the loop if one was found.
+    pub fn walk(
+        &mut self,
+        mut f: impl FnMut(Entity<T, D>) -> Option<Entity<T, D>>,
+    ) -> Option<(usize, usize)> {
+        while let Some(next) = f(self.entity()) {
+            if !self.push(next) {
+                break;
+            }
+        }
+        self.cycle
+    }
+
+    /// Returns the index in the trail where the loop starts and the length of
+    /// the loop, if the walker has entered one.
+    #[must_use]
+    pub const fn cycle(&self) -> Option<(usize, usize)> {
+        self.cycle
+    }
+
+    /// Returns the states that make up the loop, if the walker has entered
+    /// one.
+    #[must_use]
+    pub fn cycle_trail(&self) -> Option<&[Entity<T, D>]> {
+        self.cycle.map(|(start, _)| &self.trail[start..])
+    }
+
+    /// Returns every position the walker has been at, ignoring direction.
+    #[must_use]
+    pub fn positions(&self) -> HashSet<Point2<T>> {
+        self.trail.iter().map(|en| en.pos()).collect()
+    }
+}
+
+impl Walker<usize, Dir> {
+    /// Draws the trail onto a grid. Cells visited facing a single direction
+    /// get an arrow, and cells visited in several directions get '|', '-' or
+    /// '+' depending on whether the walker passed vertically, horizontally or
+    /// both.
+    pub fn render(&self, grid: &mut Grid<char>) {
+        let mut dirs: HashMap<Point2<usize>, Vec<Dir>> = HashMap::new();
+        for en in &self.trail {
+            let d = dirs.entry(en.pos()).or_default();
+            if !d.contains(&en.dir()) {
+                d.push(en.dir());
+            }
+        }
+        for (p, d) in dirs {
+            let vertical = d.iter().any(|d| matches!(d, Dir::North | Dir::South));
+            let horizontal = d.iter().any(|d| matches!(d, Dir::East | Dir::West));
+            let c = match (d.as_slice(), vertical, horizontal) {
+                ([Dir::North], _, _) => '^',
+                ([Dir::South], _, _) => 'v',
+                ([Dir::East], _, _) => '>',
+                ([Dir::West], _, _) => '<',
+                (_, true, true) => '+',
+                (_, true, false) => '|',
+                _ => '-',
+            };
+            grid.set(p, c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tern;
+
+    use super::*;
+
+    // the example lab from 2024 day 6
+    const LAB: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    fn patrol(grid: &Grid<char>) -> Walker<usize> {
+        let start = grid.find(&'^').unwrap();
+        let mut walker = Walker::new(Entity::new_on_grid(start, Dir::North, grid).unwrap());
+        walker.walk(|en| {
+            en.step_bounded()
+                .map(|moved| tern!(grid.get(moved.pos()) == Some(&'#'), en.turn_right(), moved))
+        });
+        walker
+    }
+
+    #[test]
+    fn exits() {
+        let grid = Grid::from_chars(LAB).unwrap();
+        let walker = patrol(&grid);
+        assert_eq!(walker.cycle(), None);
+        assert_eq!(walker.positions().len(), 41);
+        assert!(walker.visited((4, 6), Dir::North));
+        assert!(walker.visited((4, 1), Dir::East));
+        assert!(!walker.visited((4, 6), Dir::South));
+    }
+
+    #[test]
+    fn loops() {
+        let mut grid = Grid::from_chars(LAB).unwrap();
+        grid.set((3, 6), '#');
+        let walker = patrol(&grid);
+        let (start, len) = walker.cycle().unwrap();
+        assert_eq!(walker.trail().len(), start + len);
+        let cycle = walker.cycle_trail().unwrap();
+        assert_eq!(cycle.len(), len);
+        // the state after the end of the loop is the start of the loop
+        let last = *cycle.last().unwrap();
+        let next = last.step_bounded().unwrap();
+        let next = tern!(grid.get(next.pos()) == Some(&'#'), last.turn_right(), next);
+        assert_eq!(next, cycle[0]);
+    }
+
+    #[test]
+    fn push() {
+        let mut walker = Walker::new(Entity::new((0i32, 0), Dir::East));
+        assert!(walker.push(Entity::new((1, 0), Dir::East)));
+        assert!(walker.push(Entity::new((1, 0), Dir::South)));
+        assert!(!walker.push(Entity::new((1, 0), Dir::East)));
+        assert_eq!(walker.cycle(), Some((1, 2)));
+        assert_eq!(walker.trail().len(), 3);
+        assert_eq!(walker.positions().len(), 2);
+    }
+
+    #[test]
+    fn render() {
+        let grid = Grid::from_chars("#...\n...#\n....").unwrap();
+        let mut walker = Walker::new(Entity::new_on_grid((0, 2), Dir::North, &grid).unwrap());
+        walker.walk(|en| {
+            en.step_bounded()
+                .map(|moved| tern!(grid.get(moved.pos()) == Some(&'#'), en.turn_right(), moved))
+        });
+        let mut out = grid.clone();
+        walker.render(&mut out);
+        assert_eq!(out, Grid::from_chars("#...\n+>+#\n^.v.").unwrap());
+    }
+}